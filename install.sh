@@ -44,6 +44,13 @@ export JLO_HOME="\$HOME/.jlo"
 [[ -s "\$JLO_HOME/bin/jlo-init.sh" ]] && source "\$JLO_HOME/bin/jlo-init.sh"
 [[ -s "\$JLO_HOME/bin/jlo-autoload.sh" ]] && source "\$JLO_HOME/bin/jlo-autoload.sh"
 
+For fish, PowerShell, Nushell or tcsh, source jlo-init and jlo-autoload with the file extension of your shell
+instead (.fish, .ps1, .nu or .csh), e.g. in ~/.config/fish/config.fish:
+
+set -gx JLO_HOME "\$HOME/.jlo"
+source "\$JLO_HOME/bin/jlo-init.fish"
+source "\$JLO_HOME/bin/jlo-autoload.fish"
+
 Then restart your terminal or execute the above lines in your current shell session.
 
 After that, you can use the 'jlo' command to manage your Java environments.
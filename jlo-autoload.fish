@@ -0,0 +1,8 @@
+# Sets up the environment of the project, or reverts it when leaving the project. This is
+# cheap, as hook-env skips all work unless the configuration changed.
+function jlo_after_cd --on-variable PWD
+  jlo hook-env
+end
+
+# Immediate call for fresh spawned shells, which use the default Java version outside of projects
+jlo hook-env
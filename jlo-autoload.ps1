@@ -0,0 +1,17 @@
+# PowerShell has no hook for directory changes, so the prompt checks for them
+$global:JloLastDirectory = $null
+$global:JloOriginalPrompt = $function:prompt
+
+function global:prompt {
+  if ($PWD.ProviderPath -ne $global:JloLastDirectory) {
+    $global:JloLastDirectory = $PWD.ProviderPath
+    # Sets up the environment of the project, or reverts it when leaving the project. This is
+    # cheap, as hook-env skips all work unless the configuration changed.
+    jlo hook-env
+  }
+  & $global:JloOriginalPrompt
+}
+
+# Immediate call for fresh spawned shells, which use the default Java version outside of projects
+$global:JloLastDirectory = $PWD.ProviderPath
+jlo hook-env
@@ -1,17 +1,25 @@
 #!/usr/bin/env sh
 
+# Sets up the environment of the project, or reverts it when leaving the project. This is
+# cheap, as hook-env skips all work unless the configuration changed.
 jlo_after_cd() {
-  [ -f ".jlorc" ] && jlo env
+  jlo hook-env
+}
+
+# bash has no hook for directory changes, so the prompt checks for them
+jlo_prompt() {
+  [ "$PWD" = "$JLO_LAST_PWD" ] && return
+  JLO_LAST_PWD="$PWD"
+  jlo_after_cd
 }
 
 if [ -n "$ZSH_VERSION" ]; then
   autoload -U add-zsh-hook
   add-zsh-hook chpwd jlo_after_cd
-  jlo_after_cd
 elif [ -n "$BASH_VERSION" ]; then
-  PROMPT_COMMAND="jlo_after_cd; $PROMPT_COMMAND"
-  jlo_after_cd
+  PROMPT_COMMAND="jlo_prompt; $PROMPT_COMMAND"
 fi
 
-# Immediate call for fresh spawned shells
-jlo_after_cd
+# Immediate call for fresh spawned shells, which use the default Java version outside of projects
+JLO_LAST_PWD="$PWD"
+jlo hook-env
@@ -0,0 +1,15 @@
+function jlo
+  set -l J "$JLO_HOME/bin/jlo-bin"
+  switch "$argv[1]"
+    case env use deactivate hook-env
+      $J $argv --shell fish | source
+    case selfupdate
+      echo -n "Version before update: "
+      $J version
+      /bin/bash -c (curl -fsSL https://raw.githubusercontent.com/java-loader/jlo/refs/heads/main/install.sh | string collect)
+      echo -n "Version after update: "
+      $J version
+    case '*'
+      $J $argv
+  end
+end
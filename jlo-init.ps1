@@ -0,0 +1,18 @@
+function jlo {
+  $J = Join-Path $env:JLO_HOME 'bin/jlo-bin'
+  $command = $args[0]
+  if ($command -in 'env', 'use', 'deactivate', 'hook-env') {
+    $script = (& $J @args --shell pwsh) -join "`n"
+    if ($script) {
+      Invoke-Expression $script
+    }
+  } elseif ($command -eq 'selfupdate') {
+    Write-Host -NoNewline 'Version before update: '
+    & $J version
+    /bin/bash -c (Invoke-RestMethod https://raw.githubusercontent.com/java-loader/jlo/refs/heads/main/install.sh)
+    Write-Host -NoNewline 'Version after update: '
+    & $J version
+  } else {
+    & $J @args
+  }
+}
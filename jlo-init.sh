@@ -2,9 +2,9 @@
 jlo() {
   J="$JLO_HOME/bin/jlo-bin"
   case "$1" in
-    env|use)
+    env|use|deactivate|hook-env)
       # shellcheck disable=SC1090
-      . <("$J" "$@")
+      . <("$J" "$@" --shell posix)
       ;;
     selfupdate)
       echo -n "Version before update: "
@@ -1,257 +1,221 @@
-use semver_rs::compare;
+use crate::distribution::{
+    Distribution, JdkMetadata, fetch_json, find_top_level_dir, java_home_in, no_matching_jdk,
+};
+use crate::download::Checksum;
+use crate::spec::{JdkSpec, VersionReq};
 use std::env;
 use std::path::{Path, PathBuf};
 
-const MARKER_FILE: &str = ".jlo-managed";
+/// Eclipse Temurin, as published by the Adoptium API.
+pub struct Temurin;
 
-pub fn clean_jdks(jdk_base: &Path) -> Result<(), String> {
-    // collector major versions
-    let mut installed_jdks: std::collections::HashMap<i64, Vec<PathBuf>> =
-        std::collections::HashMap::new();
-    let entries = std::fs::read_dir(jdk_base)
-        .map_err(|e| format!("Can't read JDK base directory {:?}: {}", jdk_base, e))?;
+impl Distribution for Temurin {
+    fn name(&self) -> &'static str {
+        "temurin"
+    }
 
-    for entry in entries.filter_map(Result::ok) {
-        let path = entry.path();
-        if !path.is_dir() {
-            eprintln!("{:?} is not a directory", path);
-            continue;
-        }
-        let file_name = match path.file_name().and_then(|n| n.to_str()) {
-            Some(name) => name,
-            None => {
-                eprintln!("Ignoring directory with invalid name: {:?}", path);
-                continue;
-            }
-        };
-        if !path.join(MARKER_FILE).exists() {
-            // skip directories not managed by jlo
-            eprintln!("Ignoring non-jlo-managed directory: {:?}", path);
-            continue;
-        }
-        let semver = match semver_rs::parse(file_name, None) {
-            Ok(sv) => sv,
-            Err(_) => {
-                eprintln!("Ignoring non-semver directory: {:?}", path);
-                continue;
-            }
-        };
-        installed_jdks.entry(semver.major).or_default().push(path);
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        fetch_release_info("available_releases")
     }
 
-    for (major, mut paths) in installed_jdks {
-        paths.sort_by(|a, b| {
-            let a_str = a.file_name().and_then(|name| name.to_str()).unwrap_or("");
-            let b_str = b.file_name().and_then(|name| name.to_str()).unwrap_or("");
-            compare(b_str, a_str, None).unwrap()
-        });
+    fn archs(&self) -> &'static [&'static str] {
+        &[
+            "x86_64",
+            "x86",
+            "aarch64",
+            "arm",
+            "powerpc64",
+            "s390x",
+            "riscv64",
+        ]
+    }
 
-        if paths.len() <= 1 {
-            continue;
-        }
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let release = match &spec.version {
+            VersionReq::Release(release) => Some(release),
+            _ => None,
+        };
+        // Releases and early-access builds are looked up among all releases, while the latest
+        // assets come with one binary each
+        let listing = release.is_some() || spec.variant.early_access;
+
+        let api_url = if let Some(release) = release {
+            format!(
+                "https://api.adoptium.net/v3/assets/version/{version_range}?architecture={arch}&image_type={image_type}&jvm_impl={jvm_impl}&os={os}&release_type={release_type}&vendor=eclipse&page_size=1&sort_order=DESC",
+                version_range = version_range(release),
+                jvm_impl = self.jvm_impl(),
+                image_type = spec.variant.image_type(),
+                release_type = if spec.variant.early_access {
+                    "ea"
+                } else {
+                    "ga"
+                },
+                arch = jdk_arch(spec.variant.arch()),
+                os = jdk_os()
+            )
+        } else if spec.variant.early_access {
+            format!(
+                "https://api.adoptium.net/v3/assets/feature_releases/{java_version}/ea?architecture={arch}&image_type={image_type}&jvm_impl={jvm_impl}&os={os}&vendor=eclipse&page_size=1&sort_order=DESC",
+                java_version = spec.major(),
+                jvm_impl = self.jvm_impl(),
+                image_type = spec.variant.image_type(),
+                arch = jdk_arch(spec.variant.arch()),
+                os = jdk_os()
+            )
+        } else {
+            format!(
+                "https://api.adoptium.net/v3/assets/latest/{java_version}/{jvm_impl}?architecture={arch}&image_type={image_type}&os={os}&vendor=eclipse",
+                java_version = spec.major(),
+                jvm_impl = self.jvm_impl(),
+                image_type = spec.variant.image_type(),
+                arch = jdk_arch(spec.variant.arch()),
+                os = jdk_os()
+            )
+        };
 
-        let kept = paths[0].file_name().unwrap().to_str().unwrap();
-        let removed = paths[1..]
-            .iter()
-            .filter_map(|p| p.file_name().and_then(|n| n.to_str()))
-            .collect::<Vec<_>>()
-            .join(", ");
+        let json = fetch_json(&api_url)?;
+
+        let json_array = json
+            .as_array()
+            .ok_or_else(|| "Unexpected JSON structure received from API.".to_string())?;
+
+        let candidates: Vec<(&serde_json::Value, &serde_json::Value)> = if listing {
+            json_array
+                .iter()
+                .flat_map(|release| {
+                    release["binaries"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(move |binary| (release, binary))
+                })
+                .collect()
+        } else {
+            json_array
+                .iter()
+                .map(|asset| (asset, &asset["binary"]))
+                .collect()
+        };
 
-        eprintln!(
-            "Keeping {} for JDK {}, but removing: {}",
-            kept, major, removed
-        );
+        let (root_node, binary_node) = candidates
+            .into_iter()
+            .find(|(_, binary)| {
+                binary["image_type"].as_str() == Some(spec.variant.image_type())
+                    && binary["jvm_impl"].as_str() == Some(self.jvm_impl())
+            })
+            .ok_or_else(|| no_matching_jdk(&api_url))?;
+
+        let version_node = if listing {
+            &root_node["version_data"]
+        } else {
+            &root_node["version"]
+        };
 
-        for old_jdk in &paths[1..] {
-            if let Err(e) = std::fs::remove_dir_all(old_jdk) {
-                eprintln!("Error removing old JDK {:?}: {}", old_jdk, e);
-            }
+        let semver = version_node["semver"].as_str().unwrap_or("");
+        let release_name = root_node["release_name"].as_str().unwrap_or("");
+        let package_name = binary_node["package"]["name"].as_str().unwrap_or("");
+        let download_link = binary_node["package"]["link"].as_str().unwrap_or("");
+        let checksum = binary_node["package"]["checksum"].as_str().unwrap_or("");
+        if semver.is_empty()
+            || release_name.is_empty()
+            || package_name.is_empty()
+            || download_link.is_empty()
+            || checksum.is_empty()
+        {
+            return Err("Incomplete metadata received from API.".to_string());
         }
-    }
-
-    Ok(())
-}
-
-pub fn find_suitable_jdk(jdk_base: &Path, required_version: &str) -> Option<PathBuf> {
-    let entries = std::fs::read_dir(jdk_base).ok()?;
-
-    let mut matching_versions: Vec<PathBuf> = entries
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| {
-            path.is_dir()
-                && path
-                    .file_name()
-                    .and_then(|name| name.to_str())
-                    .is_some_and(|name| name.starts_with(required_version))
+        Ok(JdkMetadata {
+            semver: semver.to_string(),
+            release_name: release_name.to_string(),
+            package_name: package_name.to_string(),
+            download_link: download_link.to_string(),
+            checksum: Checksum::Sha256(checksum.to_string()),
+            variant: spec.variant.clone(),
         })
-        .collect();
-
-    matching_versions.sort_by(|a, b| {
-        let a_str = a.file_name().and_then(|name| name.to_str()).unwrap_or("");
-        let b_str = b.file_name().and_then(|name| name.to_str()).unwrap_or("");
-
-        compare(b_str, a_str, None).unwrap()
-    });
-
-    matching_versions.first().cloned()
-}
-
-pub fn find_installed_major_versions(jdk_base: &Path) -> Result<Vec<i64>, String> {
-    let mut major_versions = std::collections::HashSet::new();
+    }
 
-    let entries = std::fs::read_dir(jdk_base)
-        .map_err(|e| format!("Can't read JDK base directory {:?}: {}", jdk_base, e))?;
+    fn find_jdk_path(
+        &self,
+        jdk_metadata: &JdkMetadata,
+        extract_dir: &Path,
+    ) -> Result<PathBuf, String> {
+        // Only GA JDKs are named by the release (e.g. jdk-21.0.4+7, but jdk-21.0.4+7-jre for a
+        // JRE and jdk-26+20 for the early-access release jdk-26+20-ea-beta)
+        if jdk_metadata.variant.image_type.is_none() && !jdk_metadata.variant.early_access {
+            return java_home_in(&extract_dir.join(&jdk_metadata.release_name));
+        }
 
-    for entry in entries.filter_map(Result::ok) {
-        let path = entry.path();
-        if !path.is_dir() {
-            continue;
+        let image_root = find_top_level_dir(extract_dir)?;
+        if jdk_metadata.variant.is_runtime() {
+            java_home_in(&image_root)
+        } else {
+            Ok(image_root)
         }
-        let file_name = match path.file_name().and_then(|n| n.to_str()) {
-            Some(name) => name,
-            None => continue,
-        };
-        let semver = match semver_rs::parse(file_name, None) {
-            Ok(sv) => sv,
-            Err(_) => continue,
-        };
-        major_versions.insert(semver.major);
     }
 
-    let mut major_versions_vec: Vec<i64> = major_versions.into_iter().collect();
-    major_versions_vec.sort_unstable();
-    Ok(major_versions_vec)
-}
-
-pub fn fetch_metadata(java_version: &String) -> Result<JdkMetadata, String> {
-    let api_url = format!(
-        "https://api.adoptium.net/v3/assets/latest/{java_version}/hotspot?architecture={arch}&image_type=jdk&os={os}&vendor=eclipse",
-        java_version = java_version,
-        arch = jdk_arch(),
-        os = jdk_os()
-    );
-
-    let metadata_response = reqwest::blocking::get(&api_url)
-        .map_err(|e| format!("Could not fetch metadata from API: {}", e))?;
-
-    if !metadata_response.status().is_success() {
-        return Err(format!(
-            "Failed to fetch metadata from API: HTTP {}",
-            metadata_response.status()
-        ));
+    fn image_types(&self) -> &'static [&'static str] {
+        &["jre", "debugimage", "staticlibs", "testimage"]
     }
 
-    let json: serde_json::Value = metadata_response
-        .json()
-        .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
-
-    let json_array = json
-        .as_array()
-        .ok_or_else(|| "Unexpected JSON structure received from API.".to_string())?;
-
-    if json_array.is_empty() {
-        return Err(format!(
-            "No matching JDK found for the specified version and system architecture.\nTried to fetch metadata from: {}",
-            api_url
-        ));
+    fn supports_early_access(&self) -> bool {
+        true
     }
 
-    let root_node = json_array.first().unwrap();
+    fn supports_exact_versions(&self) -> bool {
+        true
+    }
 
-    let semver = root_node["version"]["semver"].as_str().unwrap_or("");
-    let release_name = root_node["release_name"].as_str().unwrap_or("");
-    let package_name = root_node["binary"]["package"]["name"]
-        .as_str()
-        .unwrap_or("");
-    let download_link = root_node["binary"]["package"]["link"]
-        .as_str()
-        .unwrap_or("");
-    let checksum = root_node["binary"]["package"]["checksum"]
-        .as_str()
-        .unwrap_or("");
-    if semver.is_empty()
-        || release_name.is_empty()
-        || package_name.is_empty()
-        || download_link.is_empty()
-        || checksum.is_empty()
-    {
-        return Err("Incomplete metadata received from API.".to_string());
+    // Temurin JDKs are installed by their plain semver, as they were before other
+    // distributions were supported.
+    fn install_dir_name(&self, jdk_metadata: &JdkMetadata) -> String {
+        format!(
+            "{}{}",
+            jdk_metadata.semver,
+            jdk_metadata.variant.dir_suffix()
+        )
     }
-    Ok(JdkMetadata {
-        semver: semver.to_string(),
-        release_name: release_name.to_string(),
-        package_name: package_name.to_string(),
-        download_link: download_link.to_string(),
-        checksum: checksum.to_string(),
-    })
 }
 
-pub fn install_jdk(
-    jdk_metadata: &JdkMetadata,
-    source_dir: &Path,
-    dest_dir: &Path,
-) -> Result<(), String> {
-    // Validate extracted path
-    let extracted_jdk_path = find_jdk_path(jdk_metadata, source_dir)
-        .map_err(|e| format!("Could not find JDK directory: {}", e))?;
-
-    // Create destination directory
-    eprintln!("Installing JDK to {:?}", dest_dir);
-    std::fs::create_dir_all(dest_dir.parent().unwrap()).unwrap();
-
-    // Move extracted JDK to final location
-    std::fs::rename(extracted_jdk_path, dest_dir).unwrap();
-
-    // touch a file to indicate that this directory is managed by jlo
-    std::fs::File::create(dest_dir.join(MARKER_FILE)).unwrap();
-
-    Ok(())
+/// Lists the major versions of all long-term support releases.
+pub fn available_lts_releases() -> Result<Vec<i64>, String> {
+    fetch_release_info("available_lts_releases")
 }
 
-fn find_jdk_path(jdk_metadata: &JdkMetadata, temp_dest: &Path) -> Result<PathBuf, String> {
-    let mut extracted_jdk_path = temp_dest.join(&jdk_metadata.release_name);
+/// Reads a list of major versions (e.g. `available_releases`) from the release information.
+fn fetch_release_info(field: &str) -> Result<Vec<i64>, String> {
+    let response = reqwest::blocking::get("https://api.adoptium.net/v3/info/available_releases");
 
-    // On macOS, the JDK is inside Contents/Home
-    if env::consts::OS == "macos" {
-        extracted_jdk_path = extracted_jdk_path.join("Contents").join("Home");
-    }
+    match response {
+        Ok(releases) => {
+            let json: serde_json::Value = releases
+                .json()
+                .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
+            let releases = json[field]
+                .as_array()
+                .ok_or("Unexpected JSON structure received from API.")?;
 
-    if env::consts::OS == "windows" {
-        let java_bin = extracted_jdk_path.join("bin").join("java.exe");
-        if !java_bin.exists() {
-            return Err(format!(
-                "Error: java executable is missing at: {:?}",
-                java_bin
-            ));
-        }
-    } else {
-        let java_bin = extracted_jdk_path.join("bin").join("java");
-        if !java_bin.exists() {
-            return Err(format!(
-                "Error: java executable is missing at: {:?}",
-                java_bin
-            ));
+            Ok(releases.iter().filter_map(|v| v.as_i64()).collect())
         }
+        Err(e) => Err(format!(
+            "Could not fetch available releases from API: {}",
+            e
+        )),
     }
-
-    Ok(extracted_jdk_path)
 }
 
-pub fn find_installed_jdk(jdk_metadata: &JdkMetadata, jdk_base_path: &Path) -> Option<PathBuf> {
-    let extracted_jdk_path = jdk_base_path.join(&jdk_metadata.semver);
-    match extracted_jdk_path.exists() {
-        true => Some(extracted_jdk_path),
-        false => None,
+/// Returns the (URL-encoded) version range of the release-by-version endpoint for a release.
+/// Without a build number, e.g. `21.0` or `21.0.4`, all builds of that release line match.
+fn version_range(release: &str) -> String {
+    if release.contains('+') {
+        return release.replace('+', "%2B");
     }
-}
 
-pub struct JdkMetadata {
-    pub semver: String,
-    pub release_name: String,
-    pub package_name: String,
-    pub download_link: String,
-    pub checksum: String,
+    let mut upper: Vec<u64> = release.split('.').map(|n| n.parse().unwrap_or(0)).collect();
+    if let Some(last) = upper.last_mut() {
+        *last += 1;
+    }
+    let upper: Vec<String> = upper.iter().map(u64::to_string).collect();
+    format!("%5B{}%2C{}%29", release, upper.join("."))
 }
 
 fn jdk_os() -> &'static str {
@@ -262,8 +226,8 @@ fn jdk_os() -> &'static str {
     }
 }
 
-fn jdk_arch() -> &'static str {
-    match env::consts::ARCH {
+fn jdk_arch(arch: &str) -> &str {
+    match arch {
         "x86_64" => "x64",
         "x86" => "x32",
         "powerpc64" => {
@@ -273,35 +237,9 @@ fn jdk_arch() -> &'static str {
                 "ppc64"
             }
         }
-        "s390x" | "arm" | "aarch64" => env::consts::ARCH,
+        "s390x" | "arm" | "aarch64" => arch,
         "sparc64" => "sparcv9",
         "riscv64" => "riscv64",
-        _ => panic!("Unknown ARCH: {}", env::consts::ARCH),
-    }
-}
-
-pub fn find_latest_jdk() -> Result<String, String> {
-    let response = reqwest::blocking::get("https://api.adoptium.net/v3/info/available_releases");
-
-    match response {
-        Ok(releases) => {
-            let json: serde_json::Value = releases
-                .json()
-                .map_err(|e| format!("Failed to parse JSON response: {}", e))?;
-            let available_releases = json["available_releases"]
-                .as_array()
-                .ok_or("Unexpected JSON structure received from API.")?;
-
-            let latest = match available_releases.iter().filter_map(|v| v.as_i64()).max() {
-                Some(v) => v,
-                None => return Err("No available releases found.".to_string()),
-            };
-
-            Ok(latest.to_string())
-        }
-        Err(e) => Err(format!(
-            "Could not fetch available releases from API: {}",
-            e
-        )),
+        _ => panic!("Unknown ARCH: {}", arch),
     }
 }
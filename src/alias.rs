@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+/// File within the jlo home directory defining aliases, one `name=spec` per line,
+/// e.g. `corp-lts=temurin@21:jre`.
+const ALIASES_FILE: &str = "aliases";
+
+/// Aliases built into the version syntax, which can't be redefined.
+const RESERVED_NAMES: &[&str] = &["lts", "latest"];
+
+/// Looks up the spec a user-defined alias stands for.
+///
+/// Returns `None` if the name isn't defined as an alias (or can't be one).
+pub fn lookup(name: &str) -> Result<Option<String>, String> {
+    if !is_alias_name(name) {
+        return Ok(None);
+    }
+
+    let path = aliases_file()?;
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Could not read aliases file {:?}: {}", path, e)),
+    };
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .find(|(alias, _)| alias.trim() == name)
+        .map(|(_, spec)| spec.trim().to_string()))
+}
+
+fn aliases_file() -> Result<PathBuf, String> {
+    Ok(crate::jlo_home_dir()?.join(ALIASES_FILE))
+}
+
+/// Alias names start with a letter and consist of letters, digits, `-` and `_`.
+fn is_alias_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !RESERVED_NAMES.contains(&name)
+}
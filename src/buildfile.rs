@@ -0,0 +1,100 @@
+use std::path::Path;
+
+/// Extracts the Java version from the content of a build file.
+type VersionDetector = fn(&str) -> Option<String>;
+
+/// Build files the required Java version is derived from, in order of precedence.
+const BUILD_FILES: &[(&str, VersionDetector)] = &[
+    ("pom.xml", maven_version),
+    ("build.gradle.kts", gradle_version),
+    ("build.gradle", gradle_version),
+];
+
+/// Maven properties and compiler plugin settings defining the Java version, in order of
+/// precedence. `java.version` is used by Spring Boot.
+const MAVEN_TAGS: &[&str] = &[
+    "maven.compiler.release",
+    "release",
+    "maven.compiler.source",
+    "maven.compiler.target",
+    "java.version",
+];
+
+/// Gradle settings defining the Java version, in order of precedence. Kotlin projects
+/// configure the toolchain with `jvmToolchain(21)`.
+const GRADLE_SETTINGS: &[&str] = &[
+    "JavaLanguageVersion.of",
+    "jvmToolchain",
+    "options.release",
+    "sourceCompatibility",
+    "targetCompatibility",
+];
+
+/// Returns the names of the build files the Java version may be derived from.
+pub fn file_names() -> impl Iterator<Item = &'static str> {
+    BUILD_FILES.iter().map(|(file, _)| *file)
+}
+
+/// Detects the Java major version required by a build file in the given directory.
+///
+/// Returns the name of the build file along with the version, e.g. `17` (or `1.8`, as
+/// written in older projects).
+pub fn detect(dir: &Path) -> Result<Option<(&'static str, String)>, String> {
+    for (file, version_of) in BUILD_FILES {
+        let content = match std::fs::read_to_string(dir.join(file)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Could not read '{}' file: {}", file, e)),
+        };
+        if let Some(version) = version_of(&content) {
+            return Ok(Some((file, major_version(&version))));
+        }
+    }
+    Ok(None)
+}
+
+fn maven_version(pom: &str) -> Option<String> {
+    let value = MAVEN_TAGS.iter().find_map(|tag| tag_value(pom, tag))?;
+
+    // resolve a property reference like ${java.version}
+    match value.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        Some(property) => tag_value(pom, property).map(str::to_string),
+        None => Some(value.to_string()),
+    }
+}
+
+fn tag_value<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}>", tag))? + tag.len() + 2;
+    let end = xml[start..].find("</")? + start;
+    Some(xml[start..end].trim()).filter(|value| !value.is_empty())
+}
+
+/// Reads settings like `languageVersion = JavaLanguageVersion.of(21)`,
+/// `options.release.set(21)` or `sourceCompatibility = JavaVersion.VERSION_1_8`.
+fn gradle_version(build_script: &str) -> Option<String> {
+    GRADLE_SETTINGS.iter().find_map(|setting| {
+        build_script
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .find_map(|line| {
+                let value = line.split_once(setting)?.1.trim_start();
+                let value = value
+                    .strip_prefix(".set")
+                    .unwrap_or(value)
+                    .trim_start_matches(['=', '(', ' ']);
+                let value = value.strip_prefix("JavaVersion.VERSION_").unwrap_or(value);
+                let version: String = value
+                    .trim_start_matches(['"', '\''])
+                    .chars()
+                    .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '_')
+                    .collect();
+                Some(version.replace('_', ".")).filter(|version| !version.is_empty())
+            })
+    })
+}
+
+/// Returns the major version of a Java version like `17`, or `8` for `1.8`.
+fn major_version(version: &str) -> String {
+    let version = version.strip_prefix("1.").unwrap_or(version);
+    version.split('.').next().unwrap_or_default().to_string()
+}
@@ -1,35 +1,443 @@
+use crate::buildfile;
+use crate::distribution::{self, DEFAULT_DISTRIBUTION, DISTRIBUTION_VARIABLES, pad_semver};
+use crate::spec::{JdkSpec, Variant};
+use std::cmp::Ordering;
+use std::env;
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 
-pub fn load() -> Result<String, String> {
-    let content = match std::fs::read_to_string(".jlorc") {
+const JLORC: &str = ".jlorc";
+
+/// Files the Java version is read from, in order of precedence. Besides `.jlorc`, these are
+/// the files of jenv, SDKMAN and asdf/mise. Build files are only consulted if none exists.
+const CONFIG_FILES: &[&str] = &[JLORC, ".java-version", ".sdkmanrc", ".tool-versions"];
+
+/// File within the jlo home directory storing the default spec, set by `jlo default`.
+const DEFAULT_FILE: &str = "default";
+
+/// Environment variable limiting the search for config files in parent directories: `home`
+/// (the home directory), `git` (the root of the git repository) or the path of a directory.
+const SEARCH_BOUNDARY_VARIABLE: &str = "JLO_SEARCH_BOUNDARY";
+
+/// Vendor identifiers used by SDKMAN, e.g. `21.0.2-tem`.
+const SDKMAN_VENDORS: &[(&str, &str)] = &[
+    ("tem", "temurin"),
+    ("zulu", "zulu"),
+    ("amzn", "corretto"),
+    ("graalce", "graalvm"),
+    ("librca", "liberica"),
+    ("ms", "microsoft"),
+    ("sapmchn", "sapmachine"),
+    ("sem", "semeru"),
+];
+
+/// Vendor names used by asdf, mise and jenv (if different from the distribution name), e.g.
+/// `temurin-21.0.2+13.0.LTS`. Plain OpenJDK builds are served by the default distribution.
+const VENDOR_NAMES: &[(&str, &str)] = &[
+    ("adoptopenjdk", "temurin"),
+    ("openjdk", DEFAULT_DISTRIBUTION),
+    ("graalvm-community", "graalvm"),
+    ("semeru-openj9", "semeru"),
+];
+
+/// Variables set by jlo itself, which can't be configured in `.jlorc`. Besides these, the
+/// variables of the distributions and jlo's own `JLO_*` variables are reserved.
+const RESERVED_VARIABLES: &[&str] = &["JAVA_HOME", "PATH"];
+
+/// Prefix of the variables jlo uses to track the environment it set up.
+const JLO_VARIABLE_PREFIX: &str = "JLO_";
+
+/// Project configuration, as read from `.jlorc` (or the files of other tools).
+pub struct Config {
+    pub spec: JdkSpec,
+    /// Additional environment variables to set, e.g. `MAVEN_OPTS`.
+    pub environment: Vec<(String, String)>,
+}
+
+impl From<JdkSpec> for Config {
+    fn from(spec: JdkSpec) -> Self {
+        Config {
+            spec,
+            environment: Vec::new(),
+        }
+    }
+}
+
+/// Loads the configuration from the nearest directory (the current one or a parent)
+/// containing a config file, so nested config files override outer ones. Outside of projects,
+/// the default spec is used.
+pub fn load() -> Result<Config, String> {
+    if let Some((_, config)) = load_project(false)? {
+        return Ok(config);
+    }
+
+    if let Some(spec) = load_default()? {
+        return Ok(Config::from(spec));
+    }
+
+    Err("To initialize a new config file, run: `jlo init` first. To set a default Java version instead, run: `jlo default <version>`".to_string())
+}
+
+/// Loads the configuration of the project the current directory belongs to, along with the
+/// project directory (the one containing the config or build file).
+///
+/// With `skip_invalid_build_files`, build files without a usable Java version are ignored
+/// rather than reported, as they may belong to projects not using jlo at all.
+pub fn load_project(skip_invalid_build_files: bool) -> Result<Option<(PathBuf, Config)>, String> {
+    let dirs = search_dirs()?;
+
+    for (depth, dir) in dirs.iter().enumerate() {
+        for file in CONFIG_FILES {
+            let content = match std::fs::read_to_string(dir.join(file)) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(format!("Could not read '{}' file: {}", file, e)),
+            };
+            let name = display_name(depth, file);
+            let config = match *file {
+                JLORC => parse_jlorc(&name, &content)?,
+                _ => parse_config(&name, file, &content).map(Config::from)?,
+            };
+            return Ok(Some((dir.clone(), config)));
+        }
+    }
+
+    for (depth, dir) in dirs.iter().enumerate() {
+        let spec = buildfile::detect(dir).and_then(|detected| {
+            let Some((file, version)) = detected else {
+                return Ok(None);
+            };
+            JdkSpec::parse(&version).map(Some).map_err(|e| {
+                format!(
+                    "Invalid Java version specified in '{}': {}",
+                    display_name(depth, file),
+                    e
+                )
+            })
+        });
+        match spec {
+            Ok(Some(spec)) => return Ok(Some((dir.clone(), Config::from(spec)))),
+            Ok(None) => {}
+            Err(_) if skip_invalid_build_files => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns a stamp of the files the configuration may be loaded from, consisting of their paths
+/// and modification times. It changes whenever the configuration changes (or another one
+/// applies), without parsing any file.
+pub fn stamp() -> Result<String, String> {
+    let mut files = Vec::new();
+    for dir in search_dirs()? {
+        files.extend(CONFIG_FILES.iter().map(|file| dir.join(file)));
+        files.extend(buildfile::file_names().map(|file| dir.join(file)));
+
+        // config files of parent directories don't apply
+        if CONFIG_FILES.iter().any(|file| dir.join(file).is_file()) {
+            break;
+        }
+    }
+    files.push(crate::jlo_home_dir()?.join(DEFAULT_FILE));
+
+    let mut stamp = String::new();
+    for file in files {
+        let Ok(modified) = std::fs::metadata(&file).and_then(|metadata| metadata.modified()) else {
+            continue;
+        };
+        let modified = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        stamp.push_str(&format!(
+            "{}@{};",
+            file.to_string_lossy(),
+            modified.as_nanos()
+        ));
+    }
+    Ok(stamp)
+}
+
+/// Loads the user-wide default spec, used outside of projects.
+pub fn load_default() -> Result<Option<JdkSpec>, String> {
+    let path = crate::jlo_home_dir()?.join(DEFAULT_FILE);
+    let content = match std::fs::read_to_string(&path) {
         Ok(content) => content,
-        Err(e) => {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                return Err("To initialize a new config file, run: `jlo init` first.".to_string());
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("Could not read {:?}: {}", path, e)),
+    };
+
+    JdkSpec::parse(content.trim())
+        .map(Some)
+        .map_err(|e| format!("Invalid Java version specified in {:?}: {}", path, e))
+}
+
+pub fn save_default(spec: &JdkSpec) -> Result<(), String> {
+    let jlo_home = crate::jlo_home_dir()?;
+    std::fs::create_dir_all(&jlo_home).map_err(|e| e.to_string())?;
+    std::fs::write(jlo_home.join(DEFAULT_FILE), format!("{}\n", spec)).map_err(|e| e.to_string())
+}
+
+/// Returns the current directory and its parents, up to the boundary configured by
+/// [`SEARCH_BOUNDARY_VARIABLE`] (or the filesystem root).
+fn search_dirs() -> Result<Vec<PathBuf>, String> {
+    let current_dir =
+        env::current_dir().map_err(|e| format!("Could not determine current directory: {}", e))?;
+    let boundary = env::var(SEARCH_BOUNDARY_VARIABLE).unwrap_or_default();
+
+    let mut dirs = Vec::new();
+    for dir in current_dir.ancestors() {
+        dirs.push(dir.to_path_buf());
+        let at_boundary = match boundary.as_str() {
+            "" => false,
+            "home" => env::home_dir().is_some_and(|home| dir == home),
+            "git" => dir.join(".git").exists(),
+            path => dir == Path::new(path),
+        };
+        if at_boundary {
+            break;
+        }
+    }
+    Ok(dirs)
+}
+
+/// Names a file relative to the current directory, e.g. `../.jlorc`.
+fn display_name(depth: usize, file: &str) -> String {
+    format!("{}{}", "../".repeat(depth), file)
+}
+
+/// Parses `.jlorc`, which is either structured (TOML) like
+///
+/// ```toml
+/// java = "21"
+/// distribution = "zulu"
+///
+/// [env]
+/// MAVEN_OPTS = "-Xmx2g"
+/// ```
+///
+/// or, as originally, consists of a single line with the spec, e.g. `zulu@21`.
+fn parse_jlorc(file: &str, content: &str) -> Result<Config, String> {
+    let mut settings = match content.parse::<toml::Table>() {
+        Ok(settings) if !settings.is_empty() => settings,
+        _ => return parse_config(file, JLORC, content).map(Config::from),
+    };
+
+    // checked first, as newer versions may support more settings
+    if let Some(jlo_version) = settings.remove("jlo_version") {
+        check_jlo_version(file, &setting_value(file, "jlo_version", &jlo_version)?)?;
+    }
+
+    let mut java = None;
+    let mut distribution = None;
+    let mut qualifiers = Vec::new();
+    let mut environment = Vec::new();
+    for (key, value) in &settings {
+        match key.as_str() {
+            "java" => java = Some(setting_value(file, key, value)?),
+            "distribution" => distribution = Some(setting_value(file, key, value)?),
+            "image_type" | "arch" => qualifiers.push(setting_value(file, key, value)?),
+            "env" => {
+                let variables = value
+                    .as_table()
+                    .ok_or_else(|| format!("Setting 'env' in '{}' must be a table.", file))?;
+                for (name, value) in variables {
+                    check_variable_name(file, name)?;
+                    environment.push((name.clone(), setting_value(file, name, value)?));
+                }
             }
-            return Err(format!("Could not read '.jlorc' file: {}", e));
+            _ => return Err(format!("Unknown setting '{}' in '{}'.", key, file)),
         }
+    }
+
+    let java = java.ok_or_else(|| format!("Setting 'java' is missing in '{}'.", file))?;
+    let mut spec = match distribution {
+        Some(_) if java.contains('@') => {
+            return Err(format!(
+                "Setting 'distribution' conflicts with the distribution given in setting 'java' in '{}'.",
+                file
+            ));
+        }
+        Some(distribution) => format!("{}@{}", distribution, java),
+        None => java,
     };
+    for qualifier in qualifiers {
+        spec.push_str(&format!(":{}", qualifier));
+    }
 
-    let java_version = content
-        .lines()
-        .map(str::trim)
-        .find(|line| !line.is_empty() && !line.starts_with('#'))
-        .ok_or_else(|| "File '.jlorc' is empty. Please specify a Java version.".to_string())?
-        .to_string();
+    let spec = JdkSpec::parse(&spec)
+        .map_err(|e| format!("Invalid Java version specified in '{}': {}", file, e))?;
+    Ok(Config { spec, environment })
+}
+
+/// Returns the value of a setting, which may be given as string, number or boolean.
+fn setting_value(file: &str, key: &str, value: &toml::Value) -> Result<String, String> {
+    match value {
+        toml::Value::String(value) => Ok(value.clone()),
+        toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+            Ok(value.to_string())
+        }
+        _ => Err(format!("Setting '{}' in '{}' must be a string.", key, file)),
+    }
+}
 
-    if !is_valid_version(&java_version) {
+/// Checks the minimum J'Lo version required by a project, e.g. `0.2` or `0.2.1`.
+fn check_jlo_version(file: &str, required_version: &str) -> Result<(), String> {
+    let valid = required_version
+        .split('.')
+        .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+    if !valid {
         return Err(format!(
-            "Unsupported Java version specified in '.jlorc': '{}'.",
-            java_version
+            "Invalid setting 'jlo_version' in '{}': '{}'.",
+            file, required_version
         ));
     }
 
-    Ok(java_version.to_string())
+    let version = env!("CARGO_PKG_VERSION");
+    if let Ok(Ordering::Less) = semver_rs::compare(version, &pad_semver(required_version), None) {
+        return Err(format!(
+            "This project requires J'Lo {} or newer, but {} is installed. To update, run: `jlo selfupdate`",
+            required_version, version
+        ));
+    }
+    Ok(())
+}
+
+/// Only allows names that can be exported by a shell, as the variables are evaluated by it.
+fn check_variable_name(file: &str, name: &str) -> Result<(), String> {
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        return Err(format!(
+            "Invalid environment variable name '{}' in '{}'.",
+            name, file
+        ));
+    }
+    if RESERVED_VARIABLES.contains(&name)
+        || DISTRIBUTION_VARIABLES.contains(&name)
+        || name.starts_with(JLO_VARIABLE_PREFIX)
+    {
+        return Err(format!(
+            "Environment variable '{}' is set by J'Lo and can't be configured in '{}'.",
+            name, file
+        ));
+    }
+    Ok(())
 }
 
-pub fn init_config(latest_release: String) -> Result<(), String> {
+/// Parses a config file of the given kind (e.g. `.sdkmanrc`), which is named `file` in
+/// messages.
+fn parse_config(file: &str, kind: &str, content: &str) -> Result<JdkSpec, String> {
+    let mut lines = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'));
+
+    let java_version = match kind {
+        ".sdkmanrc" => lines.find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "java").then(|| value.trim())
+        }),
+        // the first version is used; any further ones are fallbacks
+        ".tool-versions" => lines.find_map(|line| {
+            let mut fields = line.split_whitespace();
+            (fields.next() == Some("java")).then(|| fields.next())?
+        }),
+        _ => lines.next(),
+    }
+    .ok_or_else(|| format!("File '{}' is empty. Please specify a Java version.", file))?;
+
+    let spec = if kind == JLORC {
+        JdkSpec::parse(java_version)
+    } else {
+        parse_vendor_version(java_version)
+    };
+    spec.map_err(|e| format!("Invalid Java version specified in '{}': {}", file, e))
+}
+
+/// Parses a version as written by other tools, possibly qualified by a vendor, e.g. `21`,
+/// `21.0.2-tem` (SDKMAN), `temurin-21.0.2+13.0.LTS` (asdf/mise) or `temurin64-21.0.2` (jenv).
+///
+/// Exact versions of distributions that only support major versions are reduced to the
+/// major version, as their numbering often differs (e.g. `zulu-21.32.17`).
+fn parse_vendor_version(java_version: &str) -> Result<JdkSpec, String> {
+    let sdkman_vendor = java_version
+        .rsplit_once('-')
+        .filter(|(version, _)| version.starts_with(|c: char| c.is_ascii_digit()))
+        .and_then(|(version, vendor)| {
+            SDKMAN_VENDORS
+                .iter()
+                .find(|(id, _)| *id == vendor)
+                .map(|(_, distribution)| (*distribution, version))
+        });
+
+    let (distribution_name, version) = match sdkman_vendor {
+        Some(vendor_version) => vendor_version,
+        None => match java_version
+            .match_indices('-')
+            .find(|(i, _)| java_version[i + 1..].starts_with(|c: char| c.is_ascii_digit()))
+        {
+            Some((i, _)) if !java_version.starts_with(|c: char| c.is_ascii_digit()) => {
+                // jenv appends the bitness, e.g. temurin64
+                let vendor = java_version[..i].trim_end_matches("64");
+                let distribution = VENDOR_NAMES
+                    .iter()
+                    .find(|(name, _)| *name == vendor)
+                    .map_or(vendor, |(_, distribution)| *distribution);
+                (distribution, &java_version[i + 1..])
+            }
+            _ => (DEFAULT_DISTRIBUTION, java_version),
+        },
+    };
+
+    let distribution = distribution::by_name(distribution_name)
+        .ok_or_else(|| format!("Unknown vendor in version: '{}'.", java_version))?;
+
+    // SDKMAN marks JavaFX bundles, e.g. 21.0.2.fx-librca
+    let (version, qualifier) = match version.strip_suffix(".fx") {
+        Some(version) => (version, ":full"),
+        None => (version, ""),
+    };
+
+    let version = if version.starts_with("1.8") {
+        // Java 8 was also versioned as 1.8.0_392
+        "8".to_string()
+    } else if distribution.supports_exact_versions() {
+        exact_version(version)
+    } else {
+        version
+            .split(|c: char| !c.is_ascii_digit())
+            .next()
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    JdkSpec::parse(&format!("{}@{}{}", distribution_name, version, qualifier))
+}
+
+/// Reduces a version to its core and build number, e.g. `21.0.2+13` for `21.0.2+13.0.LTS`.
+fn exact_version(version: &str) -> String {
+    let (core, build) = match version.split_once('+') {
+        Some((core, build)) => (core, Some(build)),
+        None => (version, None),
+    };
+    let build = build
+        .map(|build| {
+            build
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect::<String>()
+        })
+        .filter(|number| !number.is_empty());
+    match build {
+        Some(build) => format!("{}+{}", core, build),
+        None => core.to_string(),
+    }
+}
+
+pub fn init_config(spec: &JdkSpec) -> Result<(), String> {
     let mut file = OpenOptions::new()
         .write(true)
         .create_new(true)
@@ -47,16 +455,40 @@ pub fn init_config(latest_release: String) -> Result<(), String> {
         "# Java version configured by J'Lo - https://github.com/java-loader/jlo"
     )
     .map_err(|e| e.to_string())?;
-    writeln!(file, "{}", latest_release).map_err(|e| e.to_string())?;
+    for (key, value) in settings(spec) {
+        writeln!(file, "{} = {}", key, toml::Value::String(value)).map_err(|e| e.to_string())?;
+    }
 
-    println!("Created config file '.jlorc' with Java {}", latest_release);
+    println!("Created config file '.jlorc' with Java {}", spec);
     Ok(())
 }
 
-pub fn is_valid_version(version: &str) -> bool {
-    if let Ok(ver) = version.parse::<u32>() {
-        ver >= 8
-    } else {
-        false
+/// Splits a spec into the settings of a structured `.jlorc`, e.g. `zulu@21:jre` into
+/// `java = "21"`, `distribution = "zulu"` and `image_type = "jre"`.
+fn settings(spec: &JdkSpec) -> Vec<(&'static str, String)> {
+    if let Some(alias) = &spec.alias {
+        return vec![("java", alias.clone())];
+    }
+
+    let version = JdkSpec {
+        distribution: None,
+        variant: Variant {
+            image_type: None,
+            arch: None,
+            ..spec.variant.clone()
+        },
+        ..spec.clone()
+    };
+
+    let mut settings = vec![("java", version.to_string())];
+    if let Some(distribution) = &spec.distribution {
+        settings.push(("distribution", distribution.clone()));
+    }
+    if let Some(image_type) = &spec.variant.image_type {
+        settings.push(("image_type", image_type.clone()));
+    }
+    if let Some(arch) = &spec.variant.arch {
+        settings.push(("arch", arch.clone()));
     }
+    settings
 }
@@ -0,0 +1,112 @@
+use crate::distribution::{
+    Distribution, JdkMetadata, archive_type, fetch_json, fetch_text, no_matching_jdk,
+    resolve_redirect,
+};
+use crate::download::Checksum;
+use crate::spec::JdkSpec;
+use std::collections::BTreeSet;
+use std::env;
+
+const DOWNLOAD_BASE_URL: &str = "https://corretto.aws/downloads";
+
+/// Amazon Corretto, as published on corretto.aws.
+///
+/// Corretto has no metadata API. The `latest` download links redirect to the
+/// versioned archive, which is where the version is taken from.
+pub struct Corretto;
+
+impl Distribution for Corretto {
+    fn name(&self) -> &'static str {
+        "corretto"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        // Each major version is maintained in its own repository, e.g. corretto/corretto-21
+        let json = fetch_json("https://api.github.com/orgs/corretto/repos?per_page=100")?;
+
+        let repos = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        let majors: BTreeSet<i64> = repos
+            .iter()
+            .filter_map(|repo| repo["name"].as_str())
+            .filter_map(|name| name.strip_prefix("corretto-"))
+            .filter_map(|major| major.parse().ok())
+            .collect();
+
+        Ok(majors.into_iter().collect())
+    }
+
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "x86", "aarch64", "arm"]
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let package_name = format!(
+            "amazon-corretto-{}-{}-{}-jdk.{}",
+            spec.major(),
+            corretto_arch(spec.variant.arch())?,
+            corretto_os(),
+            archive_type()
+        );
+        let latest_url = format!("{}/latest/{}", DOWNLOAD_BASE_URL, package_name);
+
+        // e.g. https://corretto.aws/downloads/resources/21.0.4.7.1/amazon-corretto-21.0.4.7.1-linux-x64.tar.gz
+        let download_link =
+            resolve_redirect(&latest_url)?.ok_or_else(|| no_matching_jdk(&latest_url))?;
+        let corretto_version = download_link
+            .split('/')
+            .rev()
+            .nth(1)
+            .ok_or("Incomplete metadata received from API.")?;
+        let semver = to_semver(corretto_version)
+            .ok_or_else(|| format!("Unexpected Corretto version: '{}'", corretto_version))?;
+
+        let checksum_url = format!("{}/latest_sha256/{}", DOWNLOAD_BASE_URL, package_name);
+        let checksum = fetch_text(&checksum_url)?.trim().to_string();
+
+        Ok(JdkMetadata {
+            semver,
+            release_name: format!("amazon-corretto-{}", corretto_version),
+            package_name,
+            download_link,
+            checksum: Checksum::Sha256(checksum),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+/// Converts a Corretto version to semver, e.g. `21.0.4.7.1` to `21.0.4+7.1`
+/// and `8.422.05.1` (Java 8 update 422, build 5) to `8.0.422+5.1`.
+fn to_semver(corretto_version: &str) -> Option<String> {
+    let parts: Vec<u64> = corretto_version
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect::<Option<_>>()?;
+
+    match parts.as_slice() {
+        [8, update, build, revision] => Some(format!("8.0.{}+{}.{}", update, build, revision)),
+        [major, minor, patch, build, revision, ..] => Some(format!(
+            "{}.{}.{}+{}.{}",
+            major, minor, patch, build, revision
+        )),
+        _ => None,
+    }
+}
+
+fn corretto_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn corretto_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "x86" => Ok("x86"),
+        "arm" | "aarch64" => Ok(arch),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
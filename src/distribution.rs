@@ -0,0 +1,285 @@
+use crate::adoptium::Temurin;
+use crate::corretto::Corretto;
+use crate::download::Checksum;
+use crate::graalvm::GraalVm;
+use crate::liberica::Liberica;
+use crate::microsoft::Microsoft;
+use crate::sapmachine::SapMachine;
+use crate::semeru::Semeru;
+use crate::spec::{JdkSpec, Variant};
+use crate::zulu::Zulu;
+use reqwest::blocking::{Client, Response};
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use std::env;
+use std::path::{Path, PathBuf};
+
+/// The distribution used when a version spec doesn't name one.
+pub const DEFAULT_DISTRIBUTION: &str = "temurin";
+
+/// Names of all supported distributions, as used in version specs like `temurin@21`.
+pub const DISTRIBUTION_NAMES: &[&str] = &[
+    "temurin",
+    "zulu",
+    "corretto",
+    "graalvm",
+    "liberica",
+    "microsoft",
+    "sapmachine",
+    "semeru",
+];
+
+/// Environment variables that distributions may set in addition to `JAVA_HOME`.
+pub const DISTRIBUTION_VARIABLES: &[&str] = &["GRAALVM_HOME"];
+
+pub trait Distribution {
+    /// Name of the distribution, as used in version specs like `temurin@21`.
+    fn name(&self) -> &'static str;
+
+    /// Lists all major versions available for download.
+    fn available_releases(&self) -> Result<Vec<i64>, String>;
+
+    /// The JVM implementation this distribution ships.
+    fn jvm_impl(&self) -> &'static str {
+        "hotspot"
+    }
+
+    /// Bundle types (besides the standard one) this distribution offers, e.g. `full` for a
+    /// JDK bundled with JavaFX.
+    fn bundle_types(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Image types (besides `jdk`) this distribution offers, e.g. `jre`.
+    fn image_types(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// CPU architectures (as named by Rust) this distribution offers, which may be requested
+    /// by a qualifier, e.g. to use an x86_64 JDK on Apple silicon.
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "aarch64"]
+    }
+
+    fn supports_early_access(&self) -> bool {
+        false
+    }
+
+    /// Whether a spec may pin an exact build like `21.0.4+7`, rather than a major version.
+    fn supports_exact_versions(&self) -> bool {
+        false
+    }
+
+    /// Resolves a spec to the metadata of the most recent matching build (or the pinned one).
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String>;
+
+    /// Locates the JDK root (the directory containing `bin/java`) within the extracted archive.
+    ///
+    /// By default, the archive is expected to contain exactly one top-level directory.
+    fn find_jdk_path(
+        &self,
+        _jdk_metadata: &JdkMetadata,
+        extract_dir: &Path,
+    ) -> Result<PathBuf, String> {
+        java_home_in(&find_top_level_dir(extract_dir)?)
+    }
+
+    /// Name of the directory (within the JDK base directory) the JDK is installed to.
+    fn install_dir_name(&self, jdk_metadata: &JdkMetadata) -> String {
+        format!(
+            "{}-{}{}",
+            self.name(),
+            jdk_metadata.semver,
+            jdk_metadata.variant.dir_suffix()
+        )
+    }
+
+    /// Additional environment variables to set for a JDK of this distribution.
+    /// Each variable must be listed in [`DISTRIBUTION_VARIABLES`].
+    fn environment(&self, _java_home: &Path) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn find_latest_release(&self) -> Result<i64, String> {
+        self.available_releases()?
+            .into_iter()
+            .max()
+            .ok_or_else(|| "No available releases found.".to_string())
+    }
+}
+
+pub struct JdkMetadata {
+    pub semver: String,
+    /// Vendor specific name of the release, e.g. `jdk-21.0.4+7` for Temurin.
+    pub release_name: String,
+    pub package_name: String,
+    pub download_link: String,
+    pub checksum: Checksum,
+    pub variant: Variant,
+}
+
+pub fn by_name(name: &str) -> Option<Box<dyn Distribution>> {
+    match name {
+        "temurin" => Some(Box::new(Temurin)),
+        "zulu" => Some(Box::new(Zulu)),
+        "corretto" => Some(Box::new(Corretto)),
+        "graalvm" => Some(Box::new(GraalVm)),
+        "liberica" => Some(Box::new(Liberica)),
+        "microsoft" => Some(Box::new(Microsoft)),
+        "sapmachine" => Some(Box::new(SapMachine)),
+        "semeru" => Some(Box::new(Semeru)),
+        _ => None,
+    }
+}
+
+/// Returns the distribution used for a JVM implementation if a spec doesn't name one.
+pub fn default_for_jvm_impl(jvm_impl: &str) -> &'static str {
+    DISTRIBUTION_NAMES
+        .iter()
+        .copied()
+        .find(|name| by_name(name).is_some_and(|d| d.jvm_impl() == jvm_impl))
+        .unwrap_or(DEFAULT_DISTRIBUTION)
+}
+
+/// HTTP client for metadata requests. Some APIs (e.g. GitHub) reject requests without a user agent.
+pub fn http_client() -> Client {
+    Client::builder()
+        .user_agent(concat!("jlo/", env!("CARGO_PKG_VERSION")))
+        .build()
+        .expect("Could not create HTTP client")
+}
+
+fn fetch(url: &str) -> Result<Response, String> {
+    let response = http_client()
+        .get(url)
+        .send()
+        .map_err(|e| format!("Could not fetch metadata from API: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to fetch metadata from API: HTTP {}",
+            response.status()
+        ));
+    }
+
+    Ok(response)
+}
+
+/// Fetches and parses a JSON document from a distribution's metadata API.
+pub fn fetch_json(api_url: &str) -> Result<serde_json::Value, String> {
+    fetch(api_url)?
+        .json()
+        .map_err(|e| format!("Failed to parse JSON response: {}", e))
+}
+
+/// Fetches a plain text document, e.g. a published checksum.
+pub fn fetch_text(url: &str) -> Result<String, String> {
+    fetch(url)?
+        .text()
+        .map_err(|e| format!("Failed to read response: {}", e))
+}
+
+/// Fetches a checksum file, which contains the bare checksum (possibly followed by the file name).
+pub fn fetch_checksum(url: &str) -> Result<String, String> {
+    fetch_text(url)?
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+        .ok_or_else(|| "Incomplete metadata received from API.".to_string())
+}
+
+/// Pads a Java version to a full semver, e.g. `25` to `25.0.0`.
+pub fn pad_semver(version: &str) -> String {
+    let mut parts: Vec<&str> = version.split('.').collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    parts.join(".")
+}
+
+/// Resolves a "latest" download link to the versioned link it redirects to.
+///
+/// Returns `None` if the link doesn't redirect, i.e. there is no such download.
+pub fn resolve_redirect(url: &str) -> Result<Option<String>, String> {
+    let client = Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .head(url)
+        .send()
+        .map_err(|e| format!("Could not fetch metadata from {}: {}", url, e))?;
+
+    if !response.status().is_redirection() {
+        return Ok(None);
+    }
+
+    response
+        .headers()
+        .get(LOCATION)
+        .and_then(|location| location.to_str().ok())
+        .map(|location| Some(location.to_string()))
+        .ok_or_else(|| "Incomplete metadata received from API.".to_string())
+}
+
+/// Archive format the distributions publish for the current OS.
+pub fn archive_type() -> &'static str {
+    match env::consts::OS {
+        "windows" => "zip",
+        _ => "tar.gz",
+    }
+}
+
+/// Error for a metadata request that didn't yield a JDK for the spec and architecture.
+pub fn no_matching_jdk(url: &str) -> String {
+    format!(
+        "No matching JDK found for the specified version and system architecture.\nTried to fetch metadata from: {}",
+        url
+    )
+}
+
+/// Returns the only directory within the extracted archive.
+pub fn find_top_level_dir(extract_dir: &Path) -> Result<PathBuf, String> {
+    let entries = std::fs::read_dir(extract_dir)
+        .map_err(|e| format!("Can't read directory {:?}: {}", extract_dir, e))?;
+
+    let dirs: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+
+    match dirs.as_slice() {
+        [dir] => Ok(dir.clone()),
+        [] => Err(format!("No directory found in {:?}", extract_dir)),
+        _ => Err(format!(
+            "Multiple directories found in {:?}, expected exactly one",
+            extract_dir
+        )),
+    }
+}
+
+/// Returns the Java home within the given JDK root, after verifying that it contains a java executable.
+pub fn java_home_in(jdk_root: &Path) -> Result<PathBuf, String> {
+    let mut java_home = jdk_root.to_path_buf();
+
+    // On macOS, the JDK is inside Contents/Home
+    if env::consts::OS == "macos" && java_home.join("Contents").join("Home").is_dir() {
+        java_home = java_home.join("Contents").join("Home");
+    }
+
+    let java_bin = if env::consts::OS == "windows" {
+        java_home.join("bin").join("java.exe")
+    } else {
+        java_home.join("bin").join("java")
+    };
+
+    if !java_bin.exists() {
+        return Err(format!(
+            "Error: java executable is missing at: {:?}",
+            java_bin
+        ));
+    }
+
+    Ok(java_home)
+}
@@ -1,15 +1,38 @@
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::blocking::Client;
+use sha1::Sha1;
+use sha2::digest::DynDigest;
 use sha2::{Digest, Sha256};
 use std::error::Error;
 use std::fs::File;
 use std::io::{Read, Write};
 use std::time::Duration;
 
+/// Published checksum of a download (hex encoded).
+pub enum Checksum {
+    Sha256(String),
+    Sha1(String),
+}
+
+impl Checksum {
+    fn hasher(&self) -> Box<dyn DynDigest> {
+        match self {
+            Checksum::Sha256(_) => Box::new(Sha256::new()),
+            Checksum::Sha1(_) => Box::new(Sha1::new()),
+        }
+    }
+
+    fn hex(&self) -> &str {
+        match self {
+            Checksum::Sha256(hex) | Checksum::Sha1(hex) => hex,
+        }
+    }
+}
+
 pub fn download(
     name: &str,
     url: &str,
-    expected_checksum: &str,
+    expected_checksum: &Checksum,
     file: &mut File,
 ) -> Result<(), Box<dyn Error>> {
     let client = Client::builder().timeout(Duration::from_secs(60)).build()?;
@@ -33,7 +56,7 @@ pub fn download(
     );
     pb.set_message(format!("Downloading {} ...", name));
 
-    let mut hasher = Sha256::new();
+    let mut hasher = expected_checksum.hasher();
 
     let mut downloaded: u64 = 0;
     let mut buffer = [0; 8192];
@@ -50,10 +73,11 @@ pub fn download(
     pb.finish_and_clear();
 
     let hash = hex::encode(hasher.finalize());
-    if hash != expected_checksum {
+    if !hash.eq_ignore_ascii_case(expected_checksum.hex()) {
         return Err(format!(
             "Checksum mismatch: expected {}, got {}.",
-            expected_checksum, hash
+            expected_checksum.hex(),
+            hash
         )
         .into());
     }
@@ -0,0 +1,32 @@
+use crate::distribution::fetch_json;
+
+/// Fetches the most recent (non-prerelease) releases of a GitHub repository, newest first.
+pub fn fetch_releases(repo: &str) -> Result<Vec<serde_json::Value>, String> {
+    let json = fetch_json(&format!(
+        "https://api.github.com/repos/{}/releases?per_page=100",
+        repo
+    ))?;
+    let releases = json
+        .as_array()
+        .ok_or("Unexpected JSON structure received from API.")?;
+
+    Ok(releases
+        .iter()
+        .filter(|release| !release["prerelease"].as_bool().unwrap_or(false))
+        .cloned()
+        .collect())
+}
+
+pub fn tag_name(release: &serde_json::Value) -> &str {
+    release["tag_name"].as_str().unwrap_or("")
+}
+
+/// Returns the download URL of the release asset with the given name.
+pub fn asset_url(release: &serde_json::Value, name: &str) -> Option<String> {
+    release["assets"]
+        .as_array()?
+        .iter()
+        .find(|asset| asset["name"].as_str() == Some(name))
+        .and_then(|asset| asset["browser_download_url"].as_str())
+        .map(str::to_string)
+}
@@ -0,0 +1,99 @@
+use crate::distribution::{Distribution, JdkMetadata, archive_type, fetch_checksum, pad_semver};
+use crate::download::Checksum;
+use crate::github;
+use crate::spec::JdkSpec;
+use std::collections::BTreeSet;
+use std::env;
+use std::path::Path;
+
+const REPOSITORY: &str = "graalvm/graalvm-ce-builds";
+
+/// GraalVM Community Edition, as published on GitHub (graalvm/graalvm-ce-builds).
+pub struct GraalVm;
+
+impl Distribution for GraalVm {
+    fn name(&self) -> &'static str {
+        "graalvm"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let majors: BTreeSet<i64> = github::fetch_releases(REPOSITORY)?
+            .iter()
+            .filter_map(release_version)
+            .filter_map(|version| version.split('.').next()?.parse().ok())
+            .collect();
+
+        Ok(majors.into_iter().collect())
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let releases = github::fetch_releases(REPOSITORY)?;
+        let major = spec.major().to_string();
+
+        // Releases are listed newest first; tags are named like jdk-21.0.2 (or jdk-25 for a .0.0 release)
+        let (release, version) = releases
+            .iter()
+            .filter_map(|release| release_version(release).map(|version| (release, version)))
+            .find(|(_, version)| version.split('.').next() == Some(major.as_str()))
+            .ok_or_else(|| {
+                format!(
+                    "No matching JDK found for the specified version in the releases of {}.",
+                    REPOSITORY
+                )
+            })?;
+
+        let package_name = format!(
+            "graalvm-community-jdk-{}_{}-{}_bin.{}",
+            version,
+            graalvm_os(),
+            graalvm_arch(spec.variant.arch())?,
+            archive_type()
+        );
+
+        let download_link = github::asset_url(release, &package_name).ok_or_else(|| {
+            format!(
+                "No matching JDK found for the system architecture: {} is missing in release {}.",
+                package_name,
+                github::tag_name(release)
+            )
+        })?;
+        let checksum_link = github::asset_url(release, &format!("{}.sha256", package_name))
+            .ok_or("Incomplete metadata received from API.")?;
+        let checksum = fetch_checksum(&checksum_link)?;
+
+        Ok(JdkMetadata {
+            semver: pad_semver(&version),
+            release_name: github::tag_name(release).to_string(),
+            package_name,
+            download_link,
+            checksum: Checksum::Sha256(checksum),
+            variant: spec.variant.clone(),
+        })
+    }
+
+    fn environment(&self, java_home: &Path) -> Vec<(&'static str, String)> {
+        vec![("GRAALVM_HOME", java_home.to_string_lossy().into_owned())]
+    }
+}
+
+/// Returns the Java version of a release, e.g. `21.0.2` for tag `jdk-21.0.2`.
+fn release_version(release: &serde_json::Value) -> Option<String> {
+    github::tag_name(release)
+        .strip_prefix("jdk-")
+        .map(str::to_string)
+}
+
+fn graalvm_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn graalvm_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "aarch64" => Ok("aarch64"),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
@@ -0,0 +1,273 @@
+use crate::distribution::{DEFAULT_DISTRIBUTION, Distribution, JdkMetadata};
+use crate::spec::{JdkSpec, Variant, VersionReq};
+use semver_rs::compare;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const MARKER_FILE: &str = ".jlo-managed";
+
+/// A JDK installed (and managed) by jlo.
+pub struct InstalledJdk {
+    pub path: PathBuf,
+    pub distribution: String,
+    pub semver: String,
+    pub variant: Variant,
+}
+
+impl InstalledJdk {
+    fn major(&self) -> Option<i64> {
+        semver_rs::parse(&self.semver, None).ok().map(|v| v.major)
+    }
+
+    /// Returns the spec matching this and all other builds of the same major version,
+    /// e.g. `liberica@21:full`.
+    fn major_spec(&self) -> Option<JdkSpec> {
+        Some(JdkSpec {
+            distribution: Some(self.distribution.clone()).filter(|d| d != DEFAULT_DISTRIBUTION),
+            version: VersionReq::Major(self.major()?),
+            jvm_impl: None,
+            variant: self.variant.clone(),
+            alias: None,
+        })
+    }
+
+    fn matches(&self, spec: &JdkSpec) -> bool {
+        self.distribution == spec.distribution_name()
+            && self.variant == spec.variant
+            && spec.version.matches(&self.semver)
+    }
+}
+
+/// Reads the marker file of a jlo-managed JDK directory.
+///
+/// Marker files written before other distributions were supported are empty; these
+/// directories contain Temurin JDKs named by their semver.
+fn read_marker(path: &Path) -> Option<InstalledJdk> {
+    let content = std::fs::read_to_string(path.join(MARKER_FILE)).ok()?;
+
+    let mut distribution = DEFAULT_DISTRIBUTION.to_string();
+    let mut semver = path.file_name()?.to_str()?.to_string();
+    let mut variant = Variant::default();
+    for (key, value) in content.lines().filter_map(|line| line.split_once('=')) {
+        match key.trim() {
+            "distribution" => distribution = value.trim().to_string(),
+            "version" => semver = value.trim().to_string(),
+            "bundle" => variant.bundle_type = Some(value.trim().to_string()),
+            "release_type" => variant.early_access = value.trim() == "ea",
+            "image_type" => variant.image_type = Some(value.trim().to_string()),
+            "arch" => variant.arch = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+
+    Some(InstalledJdk {
+        path: path.to_path_buf(),
+        distribution,
+        semver,
+        variant,
+    })
+}
+
+pub fn find_installed_jdks(jdk_base: &Path) -> Result<Vec<InstalledJdk>, String> {
+    let entries = std::fs::read_dir(jdk_base)
+        .map_err(|e| format!("Can't read JDK base directory {:?}: {}", jdk_base, e))?;
+
+    Ok(entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| read_marker(&path))
+        .filter(|jdk| semver_rs::parse(&jdk.semver, None).is_ok())
+        .collect())
+}
+
+pub fn clean_jdks(jdk_base: &Path) -> Result<(), String> {
+    // collect JDKs per distribution, variant and major version
+    let mut installed_jdks: HashMap<JdkSpec, Vec<InstalledJdk>> = HashMap::new();
+    let entries = std::fs::read_dir(jdk_base)
+        .map_err(|e| format!("Can't read JDK base directory {:?}: {}", jdk_base, e))?;
+
+    for entry in entries.filter_map(Result::ok) {
+        let path = entry.path();
+        if !path.is_dir() {
+            eprintln!("{:?} is not a directory", path);
+            continue;
+        }
+        let jdk = match read_marker(&path) {
+            Some(jdk) => jdk,
+            None => {
+                // skip directories not managed by jlo
+                eprintln!("Ignoring non-jlo-managed directory: {:?}", path);
+                continue;
+            }
+        };
+        let major_spec = match jdk.major_spec() {
+            Some(major_spec) => major_spec,
+            None => {
+                eprintln!("Ignoring non-semver directory: {:?}", path);
+                continue;
+            }
+        };
+        installed_jdks.entry(major_spec).or_default().push(jdk);
+    }
+
+    for (major_spec, mut jdks) in installed_jdks {
+        if jdks.len() <= 1 {
+            continue;
+        }
+
+        sort_newest_first(&mut jdks);
+
+        let kept = dir_name(&jdks[0].path);
+        let removed = jdks[1..]
+            .iter()
+            .map(|jdk| dir_name(&jdk.path))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        eprintln!(
+            "Keeping {} for JDK {}, but removing: {}",
+            kept, major_spec, removed
+        );
+
+        for old_jdk in &jdks[1..] {
+            if let Err(e) = std::fs::remove_dir_all(&old_jdk.path) {
+                eprintln!("Error removing old JDK {:?}: {}", old_jdk.path, e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn find_suitable_jdk(jdk_base: &Path, spec: &JdkSpec) -> Option<PathBuf> {
+    let mut matching_jdks: Vec<InstalledJdk> = find_installed_jdks(jdk_base)
+        .ok()?
+        .into_iter()
+        .filter(|jdk| jdk.matches(spec))
+        .collect();
+
+    sort_newest_first(&mut matching_jdks);
+
+    matching_jdks.into_iter().next().map(|jdk| jdk.path)
+}
+
+/// Returns specs for the distinct major versions (per distribution and variant) of all
+/// installed JDKs.
+pub fn find_installed_major_versions(jdk_base: &Path) -> Result<Vec<JdkSpec>, String> {
+    let mut major_versions: Vec<JdkSpec> = find_installed_jdks(jdk_base)?
+        .iter()
+        .filter_map(InstalledJdk::major_spec)
+        .collect();
+
+    major_versions.sort_unstable();
+    major_versions.dedup();
+    Ok(major_versions)
+}
+
+/// Removes all installed JDKs matching the spec, except the given one.
+///
+/// Early-access builds are replaced this way, as their semver doesn't tell which build is newer.
+pub fn remove_other_builds(jdk_base: &Path, spec: &JdkSpec, keep: &Path) -> Result<(), String> {
+    for jdk in find_installed_jdks(jdk_base)? {
+        if jdk.matches(spec) && jdk.path != keep {
+            eprintln!("Removing superseded JDK {}", dir_name(&jdk.path));
+            std::fs::remove_dir_all(&jdk.path)
+                .map_err(|e| format!("Error removing old JDK {:?}: {}", jdk.path, e))?;
+        }
+    }
+    Ok(())
+}
+
+pub fn find_installed_jdk(
+    distribution: &dyn Distribution,
+    jdk_metadata: &JdkMetadata,
+    jdk_base_path: &Path,
+) -> Option<PathBuf> {
+    let extracted_jdk_path = jdk_base_path.join(distribution.install_dir_name(jdk_metadata));
+    match extracted_jdk_path.exists() {
+        true => Some(extracted_jdk_path),
+        false => None,
+    }
+}
+
+pub fn install_jdk(
+    distribution: &dyn Distribution,
+    jdk_metadata: &JdkMetadata,
+    source_dir: &Path,
+    dest_dir: &Path,
+) -> Result<(), String> {
+    // Validate extracted path
+    let extracted_jdk_path = distribution
+        .find_jdk_path(jdk_metadata, source_dir)
+        .map_err(|e| format!("Could not find JDK directory: {}", e))?;
+
+    // Create destination directory
+    eprintln!("Installing JDK to {:?}", dest_dir);
+    std::fs::create_dir_all(dest_dir.parent().unwrap()).unwrap();
+
+    // Move extracted JDK to final location
+    std::fs::rename(extracted_jdk_path, dest_dir).unwrap();
+
+    // write a marker file to indicate that this directory is managed by jlo
+    let mut marker = format!(
+        "distribution={}\nversion={}\n",
+        distribution.name(),
+        jdk_metadata.semver
+    );
+    if let Some(bundle_type) = &jdk_metadata.variant.bundle_type {
+        marker.push_str(&format!("bundle={}\n", bundle_type));
+    }
+    if jdk_metadata.variant.early_access {
+        marker.push_str("release_type=ea\n");
+    }
+    if let Some(image_type) = &jdk_metadata.variant.image_type {
+        marker.push_str(&format!("image_type={}\n", image_type));
+    }
+    if let Some(arch) = &jdk_metadata.variant.arch {
+        marker.push_str(&format!("arch={}\n", arch));
+    }
+    std::fs::write(dest_dir.join(MARKER_FILE), marker)
+        .map_err(|e| format!("Could not write marker file: {}", e))?;
+
+    Ok(())
+}
+
+/// Creates (or replaces) a symlink to a directory.
+pub fn link(target: &Path, link: &Path) -> Result<(), String> {
+    // replace the link atomically, so that it never dangles
+    let tmp_link = link.with_extension("tmp");
+    let _ = std::fs::remove_file(&tmp_link);
+    if let Some(parent) = link.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(target, &tmp_link).map_err(|e| e.to_string())?;
+    #[cfg(windows)]
+    std::os::windows::fs::symlink_dir(target, &tmp_link).map_err(|e| e.to_string())?;
+
+    std::fs::rename(&tmp_link, link).map_err(|e| e.to_string())
+}
+
+/// Writes an executable script, like the shims.
+pub fn write_script(path: &Path, script: &str) -> Result<(), String> {
+    std::fs::write(path, script).map_err(|e| format!("Could not create {:?}: {}", path, e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("Could not make {:?} executable: {}", path, e))?;
+    }
+
+    Ok(())
+}
+
+fn sort_newest_first(jdks: &mut [InstalledJdk]) {
+    jdks.sort_by(|a, b| compare(&b.semver, &a.semver, None).unwrap());
+}
+
+fn dir_name(path: &Path) -> &str {
+    path.file_name().and_then(|n| n.to_str()).unwrap_or("")
+}
@@ -0,0 +1,139 @@
+use crate::distribution::{Distribution, JdkMetadata, archive_type, fetch_json, no_matching_jdk};
+use crate::download::Checksum;
+use crate::spec::{DEFAULT_IMAGE_TYPE, JdkSpec};
+use std::collections::BTreeSet;
+use std::env;
+
+const API_URL: &str = "https://api.bell-sw.com/v1/liberica/releases";
+
+/// BellSoft Liberica, as published by the BellSoft API.
+pub struct Liberica;
+
+impl Distribution for Liberica {
+    fn name(&self) -> &'static str {
+        "liberica"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let json = fetch_json(&releases_query(
+            DEFAULT_IMAGE_TYPE,
+            None,
+            env::consts::ARCH,
+        )?)?;
+
+        let releases = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        let majors: BTreeSet<i64> = releases
+            .iter()
+            .filter_map(|release| release["featureVersion"].as_i64())
+            .collect();
+
+        Ok(majors.into_iter().collect())
+    }
+
+    // full: bundled with JavaFX, lite: reduced footprint (e.g. for containers)
+    fn bundle_types(&self) -> &'static [&'static str] {
+        &["full", "lite"]
+    }
+
+    fn image_types(&self) -> &'static [&'static str] {
+        &["jre"]
+    }
+
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "aarch64", "powerpc64", "riscv64"]
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let api_url = format!(
+            "{}&version-feature={}&version-modifier=latest",
+            releases_query(
+                spec.variant.image_type(),
+                spec.variant.bundle_type.as_deref(),
+                spec.variant.arch()
+            )?,
+            spec.major()
+        );
+        let json = fetch_json(&api_url)?;
+
+        let release = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?
+            .first()
+            .ok_or_else(|| no_matching_jdk(&api_url))?;
+
+        let package_name = release["filename"].as_str().unwrap_or("");
+        let download_link = release["downloadUrl"].as_str().unwrap_or("");
+        // The API only publishes SHA-1 checksums
+        let checksum = release["sha1"].as_str().unwrap_or("");
+        let version = release["version"].as_str().unwrap_or("");
+        let semver = match (
+            release["featureVersion"].as_i64(),
+            release["interimVersion"].as_i64(),
+            release["updateVersion"].as_i64(),
+            release["buildVersion"].as_i64(),
+        ) {
+            (Some(feature), Some(interim), Some(update), Some(build)) => {
+                format!("{}.{}.{}+{}", feature, interim, update, build)
+            }
+            _ => String::new(),
+        };
+        if semver.is_empty()
+            || package_name.is_empty()
+            || download_link.is_empty()
+            || checksum.is_empty()
+        {
+            return Err("Incomplete metadata received from API.".to_string());
+        }
+
+        Ok(JdkMetadata {
+            semver,
+            release_name: version.to_string(),
+            package_name: package_name.to_string(),
+            download_link: download_link.to_string(),
+            checksum: Checksum::Sha1(checksum.to_string()),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+/// Liberica combines image and bundle type, e.g. `jdk-full` or `jre`.
+fn releases_query(
+    image_type: &str,
+    bundle_type: Option<&str>,
+    arch: &str,
+) -> Result<String, String> {
+    let bundle_type = match bundle_type {
+        Some(bundle_type) => format!("{}-{}", image_type, bundle_type),
+        None => image_type.to_string(),
+    };
+
+    Ok(format!(
+        "{}?bitness=64&os={}&arch={}&package-type={}&bundle-type={}&installation-type=archive",
+        API_URL,
+        liberica_os(),
+        liberica_arch(arch)?,
+        archive_type(),
+        bundle_type
+    ))
+}
+
+fn liberica_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" | "solaris" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+/// Liberica names architectures by family; the bitness is passed separately.
+fn liberica_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x86"),
+        "aarch64" => Ok("arm"),
+        "powerpc64" => Ok("ppc"),
+        "riscv64" => Ok("riscv"),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
@@ -1,17 +1,38 @@
 mod adoptium;
+mod alias;
+mod buildfile;
 mod conf;
+mod corretto;
+mod distribution;
 mod download;
 mod extract;
-
-use crate::adoptium::{
-    JdkMetadata, clean_jdks, fetch_metadata, find_installed_jdk, find_installed_major_versions,
-    find_latest_jdk, find_suitable_jdk,
+mod github;
+mod graalvm;
+mod installation;
+mod liberica;
+mod microsoft;
+mod pure;
+mod sapmachine;
+mod semeru;
+mod shell;
+mod shims;
+mod spec;
+mod zulu;
+
+use crate::conf::Config;
+use crate::distribution::{
+    DEFAULT_DISTRIBUTION, DISTRIBUTION_VARIABLES, Distribution, JdkMetadata,
+};
+use crate::installation::{
+    clean_jdks, find_installed_jdk, find_installed_major_versions, find_suitable_jdk,
 };
+use crate::shell::Shell;
+use crate::spec::JdkSpec;
 use std::collections::HashSet;
 use std::env;
 use std::fs::File;
 use std::path::{Path, PathBuf};
-use std::process::exit;
+use std::process::{Command, exit};
 use tempfile::tempdir;
 
 fn main() {
@@ -32,9 +53,36 @@ fn main() {
         "init" => {
             cmd_init();
         }
+        "default" => {
+            cmd_default();
+        }
+        "deactivate" => {
+            cmd_deactivate();
+        }
+        "hook-env" => {
+            cmd_hook_env();
+        }
+        "exec" => {
+            cmd_exec();
+        }
+        "shell" => {
+            cmd_shell();
+        }
+        "shim" => {
+            cmd_shim();
+        }
+        "home" => {
+            cmd_home();
+        }
+        "which" => {
+            cmd_which();
+        }
         "update" => {
             cmd_update();
         }
+        "reshim" => {
+            cmd_reshim();
+        }
         "selfupdate" => {
             eprintln!("Self-update is handled by the jlo shell function.");
             exit(1);
@@ -53,23 +101,204 @@ fn main() {
 }
 
 fn print_usage_and_exit() -> ! {
-    eprintln!("Usage: jlo [ env | clean | init | update | selfupdate | version ]");
+    eprintln!(
+        "Usage: jlo [ env | deactivate | exec | shell | home | which | clean | init | default | update | reshim | selfupdate | version ]"
+    );
     exit(1);
 }
 
 fn cmd_env() {
-    let java_version = if env::args().len() > 2 {
-        env::args().nth(2).unwrap()
-    } else {
-        conf::load().unwrap_or_else(|e| {
+    let shell = shell();
+
+    let mut version = None;
+    let mut pure = false;
+    let mut args = env::args().skip(2);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reset" => {
+                cmd_deactivate();
+                return;
+            }
+            "--pure" => pure = true,
+            "--shell" => {
+                args.next();
+            }
+            _ if arg.starts_with("--shell=") => {}
+            _ if arg.starts_with("--") => {
+                eprintln!("Unknown option: {}", arg);
+                print_usage_and_exit()
+            }
+            _ => version = Some(arg),
+        }
+    }
+
+    let config = load_config(version.as_deref());
+    shell.print(&setup(&config.spec, &config.environment, pure));
+}
+
+fn cmd_exec() {
+    let args: Vec<String> = env::args().skip(2).collect();
+    let (version, command) = match args.iter().position(|arg| arg == "--") {
+        Some(separator) => (&args[..separator], &args[separator + 1..]),
+        None => (&args[..0], &args[..]),
+    };
+    if version.len() > 1 || command.is_empty() {
+        eprintln!("Usage: jlo exec [version] -- <command> [args...]");
+        exit(1);
+    }
+
+    let config = load_config(version.first().map(String::as_str));
+    run(&config, command);
+}
+
+fn cmd_shell() {
+    let config = load_config(env::args().nth(2).as_deref());
+    let shell = env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+    run(&config, &[shell]);
+}
+
+/// Runs a tool of the JDK configured for the current directory, as called by a shim.
+fn cmd_shim() {
+    let args: Vec<String> = env::args().skip(2).collect();
+    let Some(tool) = args.first() else {
+        eprintln!("Usage: jlo shim <tool> [args...]");
+        exit(1);
+    };
+
+    let config = load_config(None);
+    let java_home = find_or_install_jdk(&config.spec);
+    let program = java_home.join("bin").join(tool);
+    if !program.is_file() {
+        eprintln!(
+            "{} is not part of the Java installation in use ({})",
+            tool,
+            java_home.to_string_lossy()
+        );
+        exit(127);
+    }
+
+    let mut process = Command::new(&program);
+    process.args(&args[1..]).env("JAVA_HOME", &java_home);
+    for (name, value) in config.spec.distribution().environment(&java_home) {
+        process.env(name, value);
+    }
+    for (name, value) in &config.environment {
+        process.env(name, value);
+    }
+    exec(process, tool);
+}
+
+fn cmd_home() {
+    let (args, install) = query_args();
+    if args.len() > 1 {
+        eprintln!("Usage: jlo home [--install] [version]");
+        exit(1);
+    }
+
+    let java_home = query_java_home(args.first().map(String::as_str), install);
+    println!("{}", java_home.to_string_lossy());
+}
+
+fn cmd_which() {
+    let (args, install) = query_args();
+    if args.is_empty() || args.len() > 2 {
+        eprintln!("Usage: jlo which [--install] <tool> [version]");
+        exit(1);
+    }
+
+    let java_home = query_java_home(args.get(1).map(String::as_str), install);
+    let tool = java_home.join("bin").join(&args[0]);
+    if !tool.is_file() {
+        eprintln!(
+            "{} is not part of the Java installation {}",
+            args[0],
+            java_home.to_string_lossy()
+        );
+        exit(1);
+    }
+    println!("{}", tool.to_string_lossy());
+}
+
+/// Returns the arguments of a query command, and whether the `--install` option is given.
+fn query_args() -> (Vec<String>, bool) {
+    let mut args = Vec::new();
+    let mut install = false;
+    for arg in env::args().skip(2) {
+        match arg.as_str() {
+            "--install" => install = true,
+            _ if arg.starts_with("--") => {
+                eprintln!("Unknown option: {}", arg);
+                print_usage_and_exit()
+            }
+            _ => args.push(arg),
+        }
+    }
+    (args, install)
+}
+
+/// Returns the installed JDK for the given version (or the configured one). Unless `install`
+/// is set, nothing is downloaded, and jlo exits if no installed JDK matches.
+fn query_java_home(version: Option<&str>, install: bool) -> PathBuf {
+    let config = load_config(version);
+    if install {
+        return find_or_install_jdk(&config.spec);
+    }
+
+    find_suitable_jdk(&jdk_base_dir(), &config.spec).unwrap_or_else(|| {
+        eprintln!(
+            "No installed JDK matches Java {}. To install it, run: `jlo update {}`",
+            config.spec, config.spec
+        );
+        exit(1);
+    })
+}
+
+/// Returns the config for the given version, or the configuration of the project otherwise.
+fn load_config(version: Option<&str>) -> Config {
+    match version {
+        Some(version) => Config::from(parse_spec(version)),
+        None => conf::load().unwrap_or_else(|e| {
             eprintln!("Error: Could not load configuration: {}", e);
             exit(1);
-        })
-    };
+        }),
+    }
+}
+
+/// Runs a command with the environment set up for the config, leaving the environment of the
+/// calling shell untouched. Exits with the exit code of the command.
+fn run(config: &Config, command: &[String]) -> ! {
+    let java_home = find_or_install_jdk(&config.spec);
+    let path_entries = [java_home.join("bin")];
+
+    let mut process = Command::new(&command[0]);
+    process.args(&command[1..]);
+    for (name, value) in
+        environment_changes(&config.spec, &java_home, &config.environment, &path_entries)
+    {
+        match value {
+            Some(value) => process.env(name, value),
+            None => process.env_remove(name),
+        };
+    }
+    // Entries inserted by jlo before were replaced, so that running jlo within the command
+    // replaces the JDK's entry in turn
+    process.env(PATH_ENTRY_VARIABLE, &path_entries[0]);
+    exec(process, &command[0]);
+}
 
-    assert_java_version(&java_version);
+/// Runs the process in place of jlo. Exits with the exit code of the process.
+fn exec(mut process: Command, name: &str) -> ! {
+    // Replace the jlo process, so that signals reach the command directly
+    #[cfg(unix)]
+    let error = std::os::unix::process::CommandExt::exec(&mut process);
+    #[cfg(not(unix))]
+    let error = match process.status() {
+        Ok(status) => exit(status.code().unwrap_or(1)),
+        Err(e) => e,
+    };
 
-    setup(&java_version);
+    eprintln!("Error: Could not run '{}': {}", name, error);
+    exit(127);
 }
 
 fn cmd_clean() {
@@ -77,38 +306,241 @@ fn cmd_clean() {
     clean_jdks(&jdk_base).unwrap_or_else(|e| {
         eprintln!("Error: Could not clean JDKs: {}", e);
         exit(1);
-    })
+    });
+
+    update_current_link();
+    update_shims();
 }
 
 fn cmd_init() {
-    let java_version = if env::args().len() > 2 {
-        env::args().nth(2).unwrap()
+    let spec = if env::args().len() > 2 {
+        parse_spec(&env::args().nth(2).unwrap())
+    } else if let Some((file, version)) = buildfile::detect(Path::new(".")).unwrap_or_else(|e| {
+        eprintln!("Error: Could not detect Java version of build file: {}", e);
+        exit(1);
+    }) {
+        eprintln!("Using Java {} as required by '{}'", version, file);
+        parse_spec(&version)
     } else {
-        find_latest_jdk().unwrap_or_else(|e| {
+        let distribution = distribution::by_name(DEFAULT_DISTRIBUTION).unwrap();
+        let latest_release = distribution.find_latest_release().unwrap_or_else(|e| {
             eprintln!("Error: Could not fetch latest JDK version: {}", e);
             exit(1);
-        })
+        });
+        parse_spec(&latest_release.to_string())
     };
 
-    assert_java_version(&java_version);
-
-    conf::init_config(java_version).unwrap_or_else(|e| {
+    conf::init_config(&spec).unwrap_or_else(|e| {
         eprintln!("Error: Could not create config file: {}", e);
         exit(1);
     });
 }
 
+fn cmd_default() {
+    let Some(arg) = env::args().nth(2) else {
+        match conf::load_default() {
+            Ok(Some(spec)) => println!("{}", spec),
+            Ok(None) => {
+                eprintln!("No default Java version set. To set one, run: `jlo default <version>`");
+                exit(1);
+            }
+            Err(e) => {
+                eprintln!("Error: Could not load default Java version: {}", e);
+                exit(1);
+            }
+        }
+        return;
+    };
+
+    let spec = parse_spec(&arg);
+    let java_home = find_or_install_jdk(&spec);
+
+    conf::save_default(&spec).unwrap_or_else(|e| {
+        eprintln!("Error: Could not save default Java version: {}", e);
+        exit(1);
+    });
+    update_current_link();
+
+    eprintln!(
+        "Default Java version set to {} ({})",
+        spec,
+        java_home.to_string_lossy()
+    );
+}
+
+fn cmd_deactivate() {
+    let shell = shell();
+    let restored = restore_environment();
+    if restored.is_empty() {
+        eprintln!("No Java environment set up by jlo.");
+    } else {
+        eprintln!("Restored the Java environment from before jlo");
+    }
+    shell.print(&restored);
+}
+
+/// Entry point for the shell integration, run whenever the current directory changes. Sets up
+/// the environment of the project the directory belongs to and remembers the project, so that
+/// its environment is reverted when leaving it (switching to the default Java version, if set).
+///
+/// Unlike `jlo env`, it never downloads a JDK, and skips all work unless the configuration
+/// files changed (or other ones apply) since the last run.
+fn cmd_hook_env() {
+    let shell = shell();
+    let state = conf::stamp().unwrap_or_else(|e| {
+        eprintln!("Error: Could not load configuration: {}", e);
+        exit(1);
+    });
+    if env::var(HOOK_STATE_VARIABLE).is_ok_and(|last_state| last_state == state) {
+        shell.print(&[]);
+        return;
+    }
+
+    let project = conf::load_project(true).unwrap_or_else(|e| {
+        eprintln!("Error: Could not load configuration: {}", e);
+        exit(1);
+    });
+
+    let active_project = env::var_os(PROJECT_VARIABLE).map(PathBuf::from);
+    let project_dir = project.as_ref().map(|(dir, _)| dir.clone());
+    let pure = is_pure();
+    let state_variable = (HOOK_STATE_VARIABLE.to_string(), state);
+    let mut assignments = Vec::new();
+
+    // Revert the environment of the project left, before setting up another one
+    if active_project.is_some() && active_project != project_dir {
+        assignments.extend(restore_environment());
+    }
+
+    // An environment set up outside of projects by this hook came from the default, which
+    // is replaced if it has changed. Other environments set up by jlo are kept.
+    let default_active = active_project.is_none()
+        && changed_variables()
+            .iter()
+            .any(|name| name == HOOK_STATE_VARIABLE);
+
+    // The state is only remembered once set up completely, so that a JDK installed later is
+    // picked up by the next run
+    let remember_state = track_changes(vec![(
+        state_variable.0.clone(),
+        Some(state_variable.1.clone()),
+    )]);
+    let changes = if let Some((dir, config)) = project {
+        let dir = dir.to_string_lossy().into_owned();
+        let mut environment = config.environment;
+        environment.push((PROJECT_VARIABLE.to_string(), dir.clone()));
+        environment.push(state_variable);
+        // Still remember the project if its JDK is missing, so that leaving it switches to
+        // the default
+        setup_installed(&config.spec, &environment, pure)
+            .unwrap_or_else(|| track_changes(vec![(PROJECT_VARIABLE.to_string(), Some(dir))]))
+    } else if changed_variables().is_empty() || default_active {
+        match conf::load_default() {
+            Ok(Some(spec)) => setup_installed(&spec, &[state_variable], pure).unwrap_or_default(),
+            Ok(None) if active_project.is_some() => {
+                eprintln!("Restored the Java environment from before jlo");
+                remember_state
+            }
+            Ok(None) => remember_state,
+            Err(e) => {
+                eprintln!("Warning: Could not load default Java version: {}", e);
+                Vec::new()
+            }
+        }
+    } else {
+        remember_state
+    };
+    assignments.extend(changes);
+
+    shell.print(&assignments);
+}
+
+/// Like [`setup`], but never downloads a JDK. If no installed JDK matches, a hint is printed
+/// and None is returned instead.
+fn setup_installed(
+    spec: &JdkSpec,
+    environment: &[(String, String)],
+    pure: bool,
+) -> Option<Vec<(String, Option<String>)>> {
+    if find_suitable_jdk(&jdk_base_dir(), spec).is_none() {
+        eprintln!(
+            "Java {} is not installed yet. To install it, run: `jlo update {}`",
+            spec, spec
+        );
+        return None;
+    }
+
+    Some(setup(spec, environment, pure))
+}
+
+/// Returns the assignments restoring all variables changed by jlo to their original values
+/// (none, if jlo hasn't changed any variables).
+///
+/// The variables of the own process are restored as well, so that a subsequent [`setup`]
+/// starts from the original environment.
+fn restore_environment() -> Vec<(String, Option<String>)> {
+    let changed_variables = changed_variables();
+    if changed_variables.is_empty() {
+        return Vec::new();
+    }
+
+    let mut restored: Vec<(String, Option<String>)> = Vec::new();
+    for name in changed_variables {
+        let original_name = format!("{}{}", ORIGINAL_PREFIX, name);
+        let original_value = env::var(&original_name).ok();
+        let value = match name.as_str() {
+            "PATH" => Some(restore_path()),
+            _ => original_value.clone(),
+        };
+        restored.push((name, value));
+        if original_value.is_some() {
+            restored.push((original_name, None));
+        }
+    }
+    restored.push((CHANGED_VARIABLES.to_string(), None));
+
+    for (name, value) in &restored {
+        // SAFETY: jlo is single-threaded at this point
+        unsafe {
+            match value {
+                Some(value) => env::set_var(name, value),
+                None => env::remove_var(name),
+            }
+        }
+    }
+
+    restored
+}
+
+fn cmd_reshim() {
+    let jlo_home = jlo_home_dir().unwrap();
+    let jlo_bin = env::current_exe().unwrap_or_else(|e| {
+        eprintln!("Error: Could not determine path of jlo: {}", e);
+        exit(1);
+    });
+    let count = shims::reshim(&jlo_home, &jdk_base_dir(), &jlo_bin).unwrap_or_else(|e| {
+        eprintln!("Error: Could not create shims: {}", e);
+        exit(1);
+    });
+
+    eprintln!(
+        "Created {} shims in {}",
+        count,
+        shims::shims_dir(&jlo_home).to_string_lossy()
+    );
+}
+
 fn cmd_update() {
     let mut versions_to_install: HashSet<String> = HashSet::new();
 
     let args: Vec<String> = env::args().skip(2).collect();
 
     if args.is_empty() {
-        let java_version = conf::load().unwrap_or_else(|e| {
+        let config = conf::load().unwrap_or_else(|e| {
             eprintln!("Error: Could not load configuration: {}", e);
             exit(1);
         });
-        versions_to_install.insert(java_version);
+        versions_to_install.insert(config.spec.to_string());
     } else {
         if args.iter().any(|arg| arg == "all") {
             find_installed_major_versions(&jdk_base_dir())
@@ -117,14 +549,14 @@ fn cmd_update() {
                     exit(1);
                 })
                 .into_iter()
-                .for_each(|v| {
-                    versions_to_install.insert(v.to_string());
+                .for_each(|spec| {
+                    versions_to_install.insert(spec.to_string());
                 });
         }
 
         args.into_iter().filter(|arg| arg != "all").for_each(|v| {
-            if !conf::is_valid_version(&v) {
-                eprintln!("Skipping invalid version: '{}'.", v)
+            if let Err(e) = JdkSpec::parse(&v) {
+                eprintln!("Skipping invalid version: '{}'. {}", v, e)
             } else {
                 versions_to_install.insert(v);
             }
@@ -141,60 +573,256 @@ fn cmd_update() {
     versions_to_install.sort();
 
     for java_version in versions_to_install {
-        update(&java_version);
+        update(&parse_spec(&java_version));
     }
+
+    update_current_link();
 }
 
-fn update(java_version: &String) {
-    let jdk_metadata = fetch_metadata(java_version).unwrap_or_else(|e| {
+fn update(spec: &JdkSpec) {
+    let spec = &resolve_spec(spec);
+    let distribution = spec.distribution();
+    let jdk_metadata = distribution.fetch_metadata(spec).unwrap_or_else(|e| {
         eprintln!("Error: Could not fetch JDK metadata: {}", e);
         exit(1);
     });
 
     let jdk_base = jdk_base_dir();
 
-    if let Some(path) = find_installed_jdk(&jdk_metadata, &jdk_base) {
+    if let Some(path) = find_installed_jdk(distribution.as_ref(), &jdk_metadata, &jdk_base) {
         eprintln!(
             "Most recent version of JDK {} is already installed at: {}",
-            java_version,
+            spec,
             path.to_str().unwrap()
         );
     } else {
-        install_jdk(&jdk_base, &jdk_metadata).unwrap_or_else(|e| {
-            eprintln!("Error: Could not install JDK: {}", e);
-            exit(1);
-        });
+        let path =
+            install_jdk(&jdk_base, distribution.as_ref(), &jdk_metadata).unwrap_or_else(|e| {
+                eprintln!("Error: Could not install JDK: {}", e);
+                exit(1);
+            });
+
+        if spec.variant.early_access {
+            installation::remove_other_builds(&jdk_base, spec, &path).unwrap_or_else(|e| {
+                eprintln!("Error: Could not remove superseded early-access JDK: {}", e);
+            });
+        }
     }
 }
 
-fn setup(java_version: &String) {
-    let jdk_base = jdk_base_dir();
+/// Returns the assignments setting up the environment for the given spec. In pure mode, the
+/// Java tools of other installations on the `PATH` are hidden.
+fn setup(
+    spec: &JdkSpec,
+    environment: &[(String, String)],
+    pure: bool,
+) -> Vec<(String, Option<String>)> {
+    let java_home = find_or_install_jdk(spec);
+
+    let mut path_entries = vec![java_home.join("bin")];
+    if pure {
+        let jlo_home = jlo_home_dir().unwrap();
+        let bin_dir =
+            pure::prepare_bin_dir(&jlo_home, &path_without_jlo_entries()).unwrap_or_else(|e| {
+                eprintln!("Error: Could not hide other Java installations: {}", e);
+                exit(1);
+            });
+        path_entries.push(bin_dir);
+    }
 
-    let java_home = find_suitable_jdk(&jdk_base, java_version).unwrap_or_else(|| {
-        let metadata = &fetch_metadata(java_version).unwrap();
-        install_jdk(&jdk_base, metadata).unwrap()
-    });
+    let mut changes = environment_changes(spec, &java_home, environment, &path_entries);
+
+    let path_entry = env::join_paths(&path_entries)
+        .unwrap()
+        .to_string_lossy()
+        .into_owned();
+    if env::var(PATH_ENTRY_VARIABLE).ok() != Some(path_entry.clone()) {
+        changes.push((PATH_ENTRY_VARIABLE.to_string(), Some(path_entry)));
+    }
+
+    if changes.is_empty() {
+        return changes;
+    }
 
-    let mut updates = false;
+    eprintln!("Use Java from {}", java_home.to_string_lossy());
+    track_changes(changes)
+}
+
+/// Returns the variables to change (along with their new value, or None to unset them) for
+/// using the JDK at the given Java home: `JAVA_HOME`, the variables of the distribution, the
+/// configured environment and the `PATH`, with the given entries inserted.
+fn environment_changes(
+    spec: &JdkSpec,
+    java_home: &Path,
+    environment: &[(String, String)],
+    path_entries: &[PathBuf],
+) -> Vec<(String, Option<String>)> {
+    let jdk_base = jdk_base_dir();
+    let distribution = spec.distribution();
+    let mut changes: Vec<(String, Option<String>)> = Vec::new();
 
     let current_java_home = env::var("JAVA_HOME").unwrap_or_default();
     if current_java_home != java_home.to_string_lossy() {
-        updates = true;
-        println!("export JAVA_HOME=\"{}\"", java_home.to_string_lossy());
+        changes.push((
+            "JAVA_HOME".to_string(),
+            Some(java_home.to_string_lossy().into()),
+        ));
     }
 
-    let java_bin_path = java_home.join("bin").to_string_lossy().into_owned();
-    if let Some(updated_path) = update_path(&java_bin_path) {
-        updates = true;
-        println!("export PATH=\"{}\"", updated_path);
+    let variables = distribution.environment(java_home);
+    for name in DISTRIBUTION_VARIABLES {
+        let current_value = env::var_os(name);
+        match variables.iter().find(|(n, _)| n == name) {
+            Some((_, value)) => {
+                if current_value.as_deref() != Some(value.as_ref()) {
+                    changes.push((name.to_string(), Some(value.clone())));
+                }
+            }
+            None => {
+                // Only unset variables pointing to a JDK installed by jlo
+                if current_value.is_some_and(|v| Path::new(&v).starts_with(&jdk_base)) {
+                    changes.push((name.to_string(), None));
+                }
+            }
+        }
     }
 
-    if updates {
-        eprintln!("Use Java from {}", java_home.to_string_lossy());
+    for (name, value) in environment {
+        if env::var_os(name).as_deref() != Some(value.as_ref()) {
+            changes.push((name.clone(), Some(value.clone())));
+        }
     }
+
+    if let Some(updated_path) = update_path(path_entries) {
+        changes.push(("PATH".to_string(), Some(updated_path)));
+    }
+
+    changes
+}
+
+/// Adds the assignments saving the original values of the variables to change. Before a
+/// variable is changed for the first time, its original value is saved, so that
+/// `jlo deactivate` can restore it.
+fn track_changes(changes: Vec<(String, Option<String>)>) -> Vec<(String, Option<String>)> {
+    let mut changed_variables = changed_variables();
+    let changed_count = changed_variables.len();
+
+    let mut assignments = Vec::new();
+    for (name, value) in changes {
+        if !changed_variables.contains(&name) {
+            if let Some(original_value) = env::var_os(&name) {
+                let original_name = format!("{}{}", ORIGINAL_PREFIX, name);
+                assignments.push((original_name, Some(original_value.to_string_lossy().into())));
+            }
+            changed_variables.push(name.clone());
+        }
+        assignments.push((name, value));
+    }
+
+    if changed_variables.len() > changed_count {
+        assignments.push((
+            CHANGED_VARIABLES.to_string(),
+            Some(changed_variables.join(" ")),
+        ));
+    }
+
+    assignments
 }
 
-fn install_jdk(jdk_base: &Path, jdk_metadata: &JdkMetadata) -> Result<PathBuf, String> {
+/// Returns the names of the variables changed by jlo in the current shell session.
+fn changed_variables() -> Vec<String> {
+    env::var(CHANGED_VARIABLES)
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+/// Returns the newest installed JDK matching the spec, installing it first if necessary.
+fn find_or_install_jdk(spec: &JdkSpec) -> PathBuf {
+    if !spec.variant.is_runtime() {
+        eprintln!(
+            "Image type '{}' contains no Java runtime. To install it, run: `jlo update {}`",
+            spec.variant.image_type(),
+            spec
+        );
+        exit(1);
+    }
+
+    let jdk_base = jdk_base_dir();
+
+    // Prefer an installed JDK, even for ranges and aliases, which would need the API to resolve
+    find_suitable_jdk(&jdk_base, spec).unwrap_or_else(|| {
+        let spec = &resolve_spec(spec);
+        let distribution = spec.distribution();
+        let metadata = &distribution.fetch_metadata(spec).unwrap_or_else(|e| {
+            eprintln!("Error: Could not fetch JDK metadata: {}", e);
+            exit(1);
+        });
+        install_jdk(&jdk_base, distribution.as_ref(), metadata).unwrap_or_else(|e| {
+            eprintln!("Error: Could not install JDK: {}", e);
+            exit(1);
+        })
+    })
+}
+
+/// Points the `current` symlink in the jlo home directory to the newest installed JDK of the
+/// default Java version, e.g. after it has been updated.
+fn update_current_link() {
+    let spec = match conf::load_default() {
+        Ok(Some(spec)) => spec,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("Warning: Could not load default Java version: {}", e);
+            return;
+        }
+    };
+
+    if let Some(java_home) = find_suitable_jdk(&jdk_base_dir(), &spec) {
+        let link = jlo_home_dir().unwrap().join(CURRENT_LINK);
+        installation::link(&java_home, &link).unwrap_or_else(|e| {
+            eprintln!("Warning: Could not update {:?}: {}", link, e);
+        });
+    }
+}
+
+/// Returns the shell to print commands for, as given by the `--shell` option or detected.
+fn shell() -> Shell {
+    let mut args = env::args().skip(2);
+    while let Some(arg) = args.next() {
+        let name = match arg.strip_prefix("--shell=") {
+            Some(name) => Some(name.to_string()),
+            None if arg == "--shell" => args.next(),
+            None => continue,
+        };
+        return Shell::parse(&name.unwrap_or_default()).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            exit(1);
+        });
+    }
+    Shell::detect()
+}
+
+/// Rebuilds the shims after JDKs have been installed or removed, if shims are in use.
+fn update_shims() {
+    let jlo_home = jlo_home_dir().unwrap();
+    if !shims::enabled(&jlo_home) {
+        return;
+    }
+
+    let result = env::current_exe()
+        .map_err(|e| e.to_string())
+        .and_then(|jlo_bin| shims::reshim(&jlo_home, &jdk_base_dir(), &jlo_bin));
+    if let Err(e) = result {
+        eprintln!("Warning: Could not update shims: {}", e);
+    }
+}
+
+fn install_jdk(
+    jdk_base: &Path,
+    distribution: &dyn Distribution,
+    jdk_metadata: &JdkMetadata,
+) -> Result<PathBuf, String> {
     // Download JDK
     let temp_dir = tempdir().unwrap();
     let temp_file = temp_dir.path().join(&jdk_metadata.package_name);
@@ -214,17 +842,44 @@ fn install_jdk(jdk_base: &Path, jdk_metadata: &JdkMetadata) -> Result<PathBuf, S
     // Extract JDK to temp dir
     extract::extract(&temp_file, temp_dir.path()).unwrap();
 
-    let dest_dir = jdk_base.join(&jdk_metadata.semver);
-    adoptium::install_jdk(jdk_metadata, temp_dir.path(), dest_dir.as_path())
-        .map_err(|e| format!("Error: Could not install JDK: {}", e))?;
+    let dest_dir = jdk_base.join(distribution.install_dir_name(jdk_metadata));
+    installation::install_jdk(
+        distribution,
+        jdk_metadata,
+        temp_dir.path(),
+        dest_dir.as_path(),
+    )
+    .map_err(|e| format!("Error: Could not install JDK: {}", e))?;
 
     temp_dir.close().unwrap_or_else(|err| {
         eprintln!("Warning: Could not delete temporary directory: {}", err);
     });
 
+    update_shims();
+
     Ok(dest_dir)
 }
 
+/// Symlink within the jlo home directory to the JDK of the default Java version, for tools
+/// that can't run the shell integration.
+const CURRENT_LINK: &str = "current";
+
+/// Variable listing the variables changed by jlo in the current shell session.
+const CHANGED_VARIABLES: &str = "JLO_CHANGED_VARIABLES";
+
+/// Prefix of the variables holding the original values of the variables changed by jlo,
+/// e.g. `JLO_ORIG_JAVA_HOME`.
+const ORIGINAL_PREFIX: &str = "JLO_ORIG_";
+
+/// Variable holding the PATH entries inserted by jlo, separated like in PATH.
+const PATH_ENTRY_VARIABLE: &str = "JLO_PATH_ENTRY";
+
+/// Variable holding the state of the configuration files at the last run of `jlo hook-env`.
+const HOOK_STATE_VARIABLE: &str = "JLO_HOOK_STATE";
+
+/// Variable holding the directory of the project whose environment was set up by `jlo hook-env`.
+const PROJECT_VARIABLE: &str = "JLO_PROJECT_DIR";
+
 fn jlo_home_dir() -> Result<PathBuf, String> {
     if let Some(jlo_home) = env::var_os("JLO_HOME") {
         Ok(PathBuf::from(jlo_home))
@@ -243,17 +898,12 @@ fn jdk_base_dir() -> PathBuf {
     }
 }
 
-fn update_path(java_path: &str) -> Option<String> {
+fn update_path(path_entries: &[PathBuf]) -> Option<String> {
     let current_path = env::var("PATH").unwrap_or_default();
 
-    // Remove any existing J'Lo paths to avoid duplicates
-    let jlo_base = jlo_home_dir().unwrap();
-    let mut path_vector: Vec<_> = env::split_paths(&current_path)
-        .filter(|p| !p.starts_with(&jlo_base))
-        .collect();
-
-    // Insert the new path at the beginning
-    path_vector.insert(0, java_path.into());
+    // Replace the entries inserted before, so that they don't pile up when switching JDKs
+    let mut path_vector = path_without_jlo_entries();
+    path_vector.splice(0..0, path_entries.iter().cloned());
 
     // Join paths back into a single string
     let new_path = env::join_paths(path_vector)
@@ -270,12 +920,50 @@ fn update_path(java_path: &str) -> Option<String> {
     }
 }
 
-fn assert_java_version(java_version: &str) {
-    if !conf::is_valid_version(java_version) {
-        eprintln!(
-            "Unsupported version: '{}'. Only major versions 8, 11, ... are supported.",
-            java_version
-        );
-        exit(1);
+/// Returns the PATH without the entries inserted by jlo. Entries added since then are kept.
+fn restore_path() -> String {
+    env::join_paths(path_without_jlo_entries())
+        .unwrap()
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Returns the entries of the PATH, without the ones inserted by jlo. Equal entries the user
+/// added themselves are kept.
+fn path_without_jlo_entries() -> Vec<PathBuf> {
+    let current_path = env::var_os("PATH").unwrap_or_default();
+    let mut path_vector: Vec<_> = env::split_paths(&current_path).collect();
+    for entry in jlo_path_entries() {
+        if let Some(index) = path_vector.iter().position(|p| *p == entry) {
+            path_vector.remove(index);
+        }
     }
+    path_vector
+}
+
+/// Returns the PATH entries inserted by jlo: the `bin` directory of the JDK and, in pure
+/// mode, the directory hiding the Java tools of other installations.
+fn jlo_path_entries() -> Vec<PathBuf> {
+    let path_entry = env::var_os(PATH_ENTRY_VARIABLE).unwrap_or_default();
+    env::split_paths(&path_entry)
+        .filter(|entry| !entry.as_os_str().is_empty())
+        .collect()
+}
+
+fn is_pure() -> bool {
+    jlo_path_entries().contains(&pure::bin_dir(&jlo_home_dir().unwrap()))
+}
+
+fn resolve_spec(spec: &JdkSpec) -> JdkSpec {
+    spec.resolve().unwrap_or_else(|e| {
+        eprintln!("Error: Could not resolve Java version '{}': {}", spec, e);
+        exit(1);
+    })
+}
+
+fn parse_spec(spec: &str) -> JdkSpec {
+    JdkSpec::parse(spec).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        exit(1);
+    })
 }
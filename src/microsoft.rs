@@ -0,0 +1,94 @@
+use crate::distribution::{
+    Distribution, JdkMetadata, archive_type, fetch_checksum, fetch_json, no_matching_jdk,
+    resolve_redirect,
+};
+use crate::download::Checksum;
+use crate::spec::JdkSpec;
+use std::env;
+
+const DOWNLOAD_BASE_URL: &str = "https://aka.ms/download-jdk";
+
+/// Microsoft publishes its builds to the Adoptium Marketplace, whose API lists the major
+/// versions available from Microsoft (unlike aka.ms, which has no listing).
+const RELEASES_URL: &str =
+    "https://marketplace-api.adoptium.net/v1/info/available_releases/microsoft";
+
+/// Microsoft Build of OpenJDK, as published on aka.ms/download-jdk.
+///
+/// The `latest` download links redirect to the versioned archive, which is where the
+/// version is taken from.
+pub struct Microsoft;
+
+impl Distribution for Microsoft {
+    fn name(&self) -> &'static str {
+        "microsoft"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let json = fetch_json(RELEASES_URL)?;
+
+        let releases = json["available_releases"]
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        Ok(releases.iter().filter_map(|v| v.as_i64()).collect())
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let latest_url = latest_url(spec.major(), spec.variant.arch())?;
+
+        // e.g. https://download.visualstudio.microsoft.com/download/pr/.../microsoft-jdk-21.0.4-linux-x64.tar.gz
+        let download_link =
+            resolve_redirect(&latest_url)?.ok_or_else(|| no_matching_jdk(&latest_url))?;
+        let package_name = download_link
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        let version = package_name
+            .strip_prefix("microsoft-jdk-")
+            .and_then(|rest| rest.split('-').next())
+            .filter(|version| !version.is_empty())
+            .ok_or_else(|| format!("Unexpected package name: '{}'", package_name))?;
+
+        let checksum = fetch_checksum(&format!(
+            "{}/{}.sha256sum.txt",
+            DOWNLOAD_BASE_URL, package_name
+        ))?;
+
+        Ok(JdkMetadata {
+            semver: version.to_string(),
+            release_name: format!("jdk-{}", version),
+            package_name: package_name.clone(),
+            download_link,
+            checksum: Checksum::Sha256(checksum),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+fn latest_url(java_version: i64, arch: &str) -> Result<String, String> {
+    Ok(format!(
+        "{}/microsoft-jdk-{}-{}-{}.{}",
+        DOWNLOAD_BASE_URL,
+        java_version,
+        microsoft_os(),
+        microsoft_arch(arch)?,
+        archive_type()
+    ))
+}
+
+fn microsoft_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn microsoft_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "aarch64" => Ok("aarch64"),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
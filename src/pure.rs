@@ -0,0 +1,56 @@
+use crate::installation;
+use std::path::{Path, PathBuf};
+
+/// Directory within the jlo home directory containing the stubs that hide the Java tools
+/// of other installations in pure mode.
+const PURE_BIN_DIR: &str = "pure-bin";
+
+/// Prepares the directory hiding the Java tools found in the given `PATH` entries, like
+/// `/usr/bin/java` or `/usr/bin/javac`. It's placed on the `PATH` right after the JDK in use,
+/// so tools missing in that JDK (e.g. `javac` in a JRE) fail instead of falling back to
+/// another installation.
+///
+/// Stubs are only ever added, as the directory is shared by all shell sessions.
+pub fn prepare_bin_dir(jlo_home: &Path, path_entries: &[PathBuf]) -> Result<PathBuf, String> {
+    let bin_dir = bin_dir(jlo_home);
+    std::fs::create_dir_all(&bin_dir).map_err(|e| e.to_string())?;
+
+    for entry in path_entries.iter().filter(|entry| **entry != bin_dir) {
+        let Ok(files) = std::fs::read_dir(entry) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let stub = bin_dir.join(file.file_name());
+            if !stub.exists() && is_java_tool(&file.path()) {
+                write_stub(&stub)?;
+            }
+        }
+    }
+
+    Ok(bin_dir)
+}
+
+pub fn bin_dir(jlo_home: &Path) -> PathBuf {
+    jlo_home.join(PURE_BIN_DIR)
+}
+
+/// Checks whether the file is (a symlink to) a tool within the `bin` directory of a Java
+/// installation, identified by the `release` file next to it.
+fn is_java_tool(file: &Path) -> bool {
+    let Ok(file) = file.canonicalize() else {
+        return false;
+    };
+    file.parent()
+        .filter(|dir| dir.ends_with("bin"))
+        .and_then(Path::parent)
+        .is_some_and(|java_home| java_home.join("release").is_file())
+}
+
+fn write_stub(stub: &Path) -> Result<(), String> {
+    let name = stub.file_name().unwrap().to_string_lossy();
+    let script = format!(
+        "#!/bin/sh\necho \"{} is not part of the Java installation in use ($JAVA_HOME)\" >&2\nexit 127\n",
+        name
+    );
+    installation::write_script(stub, &script)
+}
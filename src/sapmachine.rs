@@ -0,0 +1,123 @@
+use crate::distribution::{
+    Distribution, JdkMetadata, archive_type, fetch_checksum, fetch_json, pad_semver,
+};
+use crate::download::Checksum;
+use crate::spec::JdkSpec;
+use std::env;
+
+/// Index of the most recent SapMachine releases of each major version, as used by
+/// sapmachine.io.
+const RELEASES_URL: &str =
+    "https://sap.github.io/SapMachine/assets/data/sapmachine-releases-latest.json";
+
+/// SapMachine, as published on GitHub (SAP/SapMachine) and listed in its release index.
+pub struct SapMachine;
+
+impl Distribution for SapMachine {
+    fn name(&self) -> &'static str {
+        "sapmachine"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let json = fetch_json(RELEASES_URL)?;
+
+        let majors = json["majors"]
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        Ok(majors
+            .iter()
+            .filter(|major| !major["ea"].as_bool().unwrap_or(false))
+            // the id is the major version, e.g. "21"
+            .filter_map(|major| major["id"].as_str()?.parse().ok())
+            .collect())
+    }
+
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "aarch64", "powerpc64"]
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let json = fetch_json(RELEASES_URL)?;
+        let platform = format!(
+            "{}-{}",
+            sapmachine_os(),
+            sapmachine_arch(spec.variant.arch())?
+        );
+
+        // Releases are listed newest first; tags are named like sapmachine-21.0.4, while
+        // early-access builds are named like sapmachine-26+20
+        let release = json["assets"][spec.major().to_string()]["releases"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|release| release_version(release).is_some())
+            .ok_or_else(|| {
+                format!(
+                    "No matching JDK found for the specified version in the SapMachine release index: {}",
+                    RELEASES_URL
+                )
+            })?;
+        let tag = release["tag"].as_str().unwrap_or_default();
+        let version = release_version(release).unwrap_or_default();
+
+        // e.g. https://github.com/SAP/SapMachine/releases/download/sapmachine-21.0.4/sapmachine-jdk-21.0.4_linux-x64_bin.tar.gz
+        let download_link = release["jdk"][&platform]
+            .as_str()
+            .filter(|link| link.ends_with(archive_type()))
+            .ok_or_else(|| {
+                format!(
+                    "No matching JDK found for the system architecture: {} is missing in release {}.",
+                    platform, tag
+                )
+            })?
+            .to_string();
+        let package_name = download_link
+            .rsplit('/')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        // The checksum is published next to the archive, e.g. sapmachine-jdk-21.0.4_linux-x64_bin.sha256.txt
+        let checksum_link = format!(
+            "{}.sha256.txt",
+            download_link
+                .strip_suffix(&format!(".{}", archive_type()))
+                .unwrap_or(&download_link)
+        );
+        let checksum = fetch_checksum(&checksum_link)?;
+
+        Ok(JdkMetadata {
+            semver: pad_semver(version),
+            release_name: tag.to_string(),
+            package_name,
+            download_link,
+            checksum: Checksum::Sha256(checksum),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+/// Returns the Java version of a GA release, e.g. `21.0.4` for tag `sapmachine-21.0.4`.
+fn release_version(release: &serde_json::Value) -> Option<&str> {
+    release["tag"]
+        .as_str()?
+        .strip_prefix("sapmachine-")
+        .filter(|version| !version.contains('+'))
+}
+
+fn sapmachine_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn sapmachine_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "aarch64" => Ok("aarch64"),
+        "powerpc64" if cfg!(target_endian = "little") => Ok("ppc64le"),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
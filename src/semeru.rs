@@ -0,0 +1,132 @@
+use crate::distribution::{Distribution, JdkMetadata, archive_type, fetch_checksum, fetch_json};
+use crate::download::Checksum;
+use crate::github;
+use crate::spec::JdkSpec;
+use std::collections::BTreeSet;
+use std::env;
+
+/// IBM Semeru Runtimes (Open Edition) with the Eclipse OpenJ9 JVM, as published on GitHub.
+///
+/// Each major version is published in its own repository, e.g. ibmruntimes/semeru21-binaries.
+pub struct Semeru;
+
+impl Distribution for Semeru {
+    fn name(&self) -> &'static str {
+        "semeru"
+    }
+
+    fn jvm_impl(&self) -> &'static str {
+        "openj9"
+    }
+
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "aarch64", "powerpc64", "s390x"]
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let json = fetch_json("https://api.github.com/orgs/ibmruntimes/repos?per_page=100")?;
+
+        let repos = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        let majors: BTreeSet<i64> = repos
+            .iter()
+            .filter_map(|repo| repo["name"].as_str())
+            .filter_map(|name| name.strip_prefix("semeru")?.strip_suffix("-binaries"))
+            .filter_map(|major| major.parse().ok())
+            .collect();
+
+        Ok(majors.into_iter().collect())
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let repository = format!("ibmruntimes/semeru{}-binaries", spec.major());
+        let releases = github::fetch_releases(&repository)?;
+
+        // Releases are listed newest first
+        let release = releases.first().ok_or_else(|| {
+            format!(
+                "No matching JDK found for the specified version in the releases of {}.",
+                repository
+            )
+        })?;
+
+        // e.g. ibm-semeru-open-jdk_x64_linux_21.0.4_7_openj9-0.46.0.tar.gz
+        let package_prefix = format!(
+            "ibm-semeru-open-jdk_{}_{}_",
+            semeru_arch(spec.variant.arch())?,
+            semeru_os()
+        );
+        let package_suffix = format!(".{}", archive_type());
+        let package_name = release["assets"]
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?
+            .iter()
+            .filter_map(|asset| asset["name"].as_str())
+            .find(|name| name.starts_with(&package_prefix) && name.ends_with(&package_suffix))
+            .ok_or_else(|| {
+                format!(
+                    "No matching JDK found for the system architecture in release {}.",
+                    github::tag_name(release)
+                )
+            })?
+            .to_string();
+
+        let download_link = github::asset_url(release, &package_name)
+            .ok_or("Incomplete metadata received from API.")?;
+        let checksum_link = github::asset_url(release, &format!("{}.sha256.txt", package_name))
+            .ok_or("Incomplete metadata received from API.")?;
+        let checksum = fetch_checksum(&checksum_link)?;
+
+        let semver = to_semver(github::tag_name(release))
+            .ok_or_else(|| format!("Unexpected release name: '{}'", github::tag_name(release)))?;
+
+        Ok(JdkMetadata {
+            semver,
+            release_name: github::tag_name(release).to_string(),
+            package_name,
+            download_link,
+            checksum: Checksum::Sha256(checksum),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+/// Converts a release tag to semver, e.g. `jdk-21.0.4+7_openj9-0.46.0` to `21.0.4+7`
+/// and `jdk8u422-b05_openj9-0.46.0` to `8.0.422+5`.
+fn to_semver(tag_name: &str) -> Option<String> {
+    let openjdk_version = tag_name.split('_').next()?;
+
+    if let Some(java8_version) = openjdk_version.strip_prefix("jdk8u") {
+        let (update, build) = java8_version.split_once("-b")?;
+        let build: u32 = build.parse().ok()?;
+        return Some(format!("8.0.{}+{}", update, build));
+    }
+
+    openjdk_version.strip_prefix("jdk-").map(str::to_string)
+}
+
+fn semeru_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "aix" => env::consts::OS,
+        "macos" => "mac",
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn semeru_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "aarch64" => Ok("aarch64"),
+        "s390x" => Ok("s390x"),
+        "powerpc64" => {
+            if cfg!(target_endian = "little") {
+                Ok("ppc64le")
+            } else {
+                Ok("ppc64")
+            }
+        }
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
@@ -0,0 +1,153 @@
+use std::env;
+use std::path::Path;
+
+/// Shell dialects the environment can be set up for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shell {
+    /// POSIX shells like bash and zsh.
+    Posix,
+    Fish,
+    /// PowerShell.
+    Pwsh,
+    /// Nushell can't evaluate commands printed by jlo, so it reads the changes as JSON.
+    Nushell,
+    /// csh and tcsh.
+    Csh,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Result<Shell, String> {
+        match name {
+            "sh" | "bash" | "zsh" | "ksh" | "dash" | "posix" => Ok(Shell::Posix),
+            "fish" => Ok(Shell::Fish),
+            "pwsh" | "powershell" => Ok(Shell::Pwsh),
+            "nu" | "nushell" => Ok(Shell::Nushell),
+            "csh" | "tcsh" => Ok(Shell::Csh),
+            _ => Err(format!(
+                "Unsupported shell: '{}'. Use bash, zsh, fish, pwsh, nu or tcsh.",
+                name
+            )),
+        }
+    }
+
+    /// Detects the shell of the user by the `SHELL` variable, defaulting to POSIX shells.
+    pub fn detect() -> Shell {
+        env::var_os("SHELL")
+            .and_then(|shell| {
+                let name = Path::new(&shell)
+                    .file_stem()?
+                    .to_string_lossy()
+                    .into_owned();
+                Shell::parse(&name).ok()
+            })
+            .unwrap_or(Shell::Posix)
+    }
+
+    /// Prints the commands setting the given variables (or unsetting them, if the value is
+    /// None), with the values quoted for the shell.
+    pub fn print(&self, assignments: &[(String, Option<String>)]) {
+        if *self == Shell::Nushell {
+            println!("{}", json(assignments));
+            return;
+        }
+
+        for (name, value) in assignments {
+            match value {
+                Some(value) => println!("{}", self.set(name, value)),
+                None => println!("{}", self.unset(name)),
+            }
+        }
+    }
+
+    fn set(&self, name: &str, value: &str) -> String {
+        match self {
+            Shell::Posix => format!("export {}={}", name, posix_quote(value)),
+            // fish keeps the PATH as a list
+            Shell::Fish if name == "PATH" => {
+                let entries: Vec<_> = env::split_paths(value)
+                    .map(|entry| fish_quote(&entry.to_string_lossy()))
+                    .collect();
+                format!("set -gx {} {}", name, entries.join(" "))
+            }
+            Shell::Fish => format!("set -gx {} {}", name, fish_quote(value)),
+            Shell::Pwsh => format!("$env:{} = {}", name, powershell_quote(value)),
+            Shell::Csh => format!("setenv {} {};", name, csh_quote(value)),
+            Shell::Nushell => unreachable!("printed as JSON"),
+        }
+    }
+
+    fn unset(&self, name: &str) -> String {
+        match self {
+            Shell::Posix => format!("unset {}", name),
+            Shell::Fish => format!("set -e {}", name),
+            Shell::Pwsh => format!("Remove-Item -ErrorAction SilentlyContinue Env:{}", name),
+            Shell::Csh => format!("unsetenv {};", name),
+            Shell::Nushell => unreachable!("printed as JSON"),
+        }
+    }
+}
+
+fn escape(value: &str, special_chars: &[char]) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        if special_chars.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quotes a value for POSIX shells, using double quotes.
+pub fn posix_quote(value: &str) -> String {
+    format!("\"{}\"", escape(value, &['"', '\\', '$', '`']))
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", escape(value, &['\'', '\\']))
+}
+
+/// Quotes a value for PowerShell, which treats typographic single quotes like `'`, too.
+fn powershell_quote(value: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in value.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Quotes a value for csh, where single quotes don't protect `!` (history substitution) and
+/// newlines.
+fn csh_quote(value: &str) -> String {
+    let mut quoted = String::from("'");
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("'\\''"),
+            '!' | '\n' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Returns the assignments as JSON object, with null for variables to unset. If a variable is
+/// assigned several times, the last assignment wins.
+fn json(assignments: &[(String, Option<String>)]) -> String {
+    let mut object = serde_json::Map::new();
+    for (name, value) in assignments {
+        let value = match value {
+            Some(value) => serde_json::Value::String(value.clone()),
+            None => serde_json::Value::Null,
+        };
+        object.insert(name.clone(), value);
+    }
+    serde_json::Value::Object(object).to_string()
+}
@@ -0,0 +1,60 @@
+use crate::installation::{self, find_installed_jdks};
+use crate::shell;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Directory within the jlo home directory containing the shims.
+const SHIMS_DIR: &str = "shims";
+
+pub fn shims_dir(jlo_home: &Path) -> PathBuf {
+    jlo_home.join(SHIMS_DIR)
+}
+
+/// Checks whether shims are in use, i.e. whether `jlo reshim` has been run before.
+pub fn enabled(jlo_home: &Path) -> bool {
+    shims_dir(jlo_home).is_dir()
+}
+
+/// Creates a shim for each tool of the installed JDKs (`java`, `javac`, `jshell`, ...), and
+/// removes the shims of tools no longer installed. Returns the number of shims.
+///
+/// A shim runs the tool of the JDK configured for the current directory, by calling
+/// `jlo shim <tool>`.
+pub fn reshim(jlo_home: &Path, jdk_base: &Path, jlo_bin: &Path) -> Result<usize, String> {
+    let dir = shims_dir(jlo_home);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Could not create {:?}: {}", dir, e))?;
+
+    let mut tools = BTreeSet::new();
+    if jdk_base.is_dir() {
+        for jdk in find_installed_jdks(jdk_base)? {
+            let Ok(files) = std::fs::read_dir(jdk.path.join("bin")) else {
+                continue;
+            };
+            tools.extend(
+                files
+                    .flatten()
+                    .filter(|file| file.path().is_file())
+                    .map(|file| file.file_name()),
+            );
+        }
+    }
+
+    let shims = std::fs::read_dir(&dir).map_err(|e| format!("Could not read {:?}: {}", dir, e))?;
+    for shim in shims.flatten() {
+        if !tools.contains(&shim.file_name()) {
+            std::fs::remove_file(shim.path())
+                .map_err(|e| format!("Could not remove {:?}: {}", shim.path(), e))?;
+        }
+    }
+
+    for tool in &tools {
+        let script = format!(
+            "#!/bin/sh\n# Generated by jlo: runs the tool of the JDK configured for the current directory\nexec {} shim {} \"$@\"\n",
+            shell::posix_quote(&jlo_bin.to_string_lossy()),
+            shell::posix_quote(&tool.to_string_lossy())
+        );
+        installation::write_script(&dir.join(tool), &script)?;
+    }
+
+    Ok(tools.len())
+}
@@ -0,0 +1,550 @@
+use crate::adoptium;
+use crate::alias;
+use crate::distribution::{
+    self, DEFAULT_DISTRIBUTION, DISTRIBUTION_NAMES, Distribution, pad_semver,
+};
+use std::env;
+use std::fmt;
+
+/// Bundle type used when a spec doesn't name one.
+pub const STANDARD_BUNDLE: &str = "standard";
+
+/// Suffix of a version requesting an early-access build, e.g. `26-ea`.
+pub const EARLY_ACCESS_SUFFIX: &str = "-ea";
+
+/// Optional prefix of an exact build, as in Temurin release names like `jdk-21.0.4+7`.
+pub const JDK_PREFIX: &str = "jdk-";
+
+/// JVM implementations that may be requested by a qualifier.
+pub const JVM_IMPLS: &[&str] = &["hotspot", "openj9"];
+
+/// Image type used when a spec doesn't name one.
+pub const DEFAULT_IMAGE_TYPE: &str = "jdk";
+
+/// Image types that contain a Java runtime, i.e. can be used as `JAVA_HOME`.
+pub const RUNTIME_IMAGE_TYPES: &[&str] = &["jdk", "jre"];
+
+/// Properties distinguishing JDKs of the same distribution and version, which are installed
+/// side by side.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variant {
+    /// Bundle type, if other than standard.
+    pub bundle_type: Option<String>,
+    /// Early-access build, published before the general availability of a release.
+    pub early_access: bool,
+    /// Image type (e.g. `jre` or `debugimage`), if other than `jdk`.
+    pub image_type: Option<String>,
+    /// CPU architecture, if other than the one jlo runs on.
+    pub arch: Option<String>,
+}
+
+impl Variant {
+    pub fn image_type(&self) -> &str {
+        self.image_type.as_deref().unwrap_or(DEFAULT_IMAGE_TYPE)
+    }
+
+    pub fn arch(&self) -> &str {
+        self.arch.as_deref().unwrap_or(env::consts::ARCH)
+    }
+
+    /// Whether the image contains a Java runtime, as opposed to e.g. debug symbols.
+    pub fn is_runtime(&self) -> bool {
+        RUNTIME_IMAGE_TYPES.contains(&self.image_type())
+    }
+
+    /// Suffix of the installation directory name, e.g. `-full`, `-jre`, `-x86_64` or `-ea`.
+    pub fn dir_suffix(&self) -> String {
+        let mut suffix = String::new();
+        if let Some(bundle_type) = &self.bundle_type {
+            suffix.push_str(&format!("-{}", bundle_type));
+        }
+        if let Some(image_type) = &self.image_type {
+            suffix.push_str(&format!("-{}", image_type));
+        }
+        if let Some(arch) = &self.arch {
+            suffix.push_str(&format!("-{}", arch));
+        }
+        if self.early_access {
+            suffix.push_str("-ea");
+        }
+        suffix
+    }
+}
+
+/// A requested version, as part of a [`JdkSpec`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum VersionReq {
+    /// Latest build of a major version, e.g. `21`.
+    Major(i64),
+    /// Latest build of a release line, e.g. `21.0` or `21.0.4`, or an exact build like
+    /// `21.0.4+7`.
+    Release(String),
+    /// Latest build of the newest major version within a range, e.g. `17+` or `>=17 <22`.
+    /// The upper bound is exclusive.
+    Range { min: Option<i64>, max: Option<i64> },
+    /// Latest build of the newest long-term support release.
+    Lts,
+    /// Latest build of the newest release.
+    Latest,
+}
+
+impl VersionReq {
+    pub fn parse(version: &str) -> Option<VersionReq> {
+        match version {
+            "lts" => return Some(VersionReq::Lts),
+            "latest" => return Some(VersionReq::Latest),
+            _ => {}
+        }
+
+        if let Some(min) = version.strip_suffix('+')
+            && is_number(min)
+        {
+            return Some(VersionReq::Range {
+                min: Some(min.parse().ok()?),
+                max: None,
+            });
+        }
+
+        if version.starts_with(['<', '>']) {
+            return parse_range(version);
+        }
+
+        let (core, build) = split_build(version);
+        let components: Vec<&str> = core.split('.').collect();
+        if !components.iter().chain(&build).all(|n| is_number(n)) || components.len() > 4 {
+            return None;
+        }
+        let major: i64 = components[0].parse().ok()?;
+        if major < 8 {
+            return None;
+        }
+
+        if components.len() == 1 && build.is_none() {
+            Some(VersionReq::Major(major))
+        } else {
+            Some(VersionReq::Release(version.to_string()))
+        }
+    }
+
+    /// Returns the major version, if the requirement is limited to one.
+    pub fn major(&self) -> Option<i64> {
+        match self {
+            VersionReq::Major(major) => Some(*major),
+            VersionReq::Release(release) => release.split(['.', '+']).next()?.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Checks whether a JDK of the given major version may satisfy the requirement.
+    ///
+    /// Long-term support releases are recognized by the OpenJDK release cadence here, so that
+    /// installed JDKs can be matched without asking the API.
+    pub fn matches_major(&self, major: i64) -> bool {
+        match self {
+            VersionReq::Major(_) | VersionReq::Release(_) => self.major() == Some(major),
+            VersionReq::Range { min, max } => {
+                min.is_none_or(|min| major >= min) && max.is_none_or(|max| major < max)
+            }
+            VersionReq::Lts => is_lts_release(major),
+            VersionReq::Latest => true,
+        }
+    }
+
+    /// Checks whether an installed JDK's semver (e.g. `21.0.4+7.0.LTS`) satisfies the
+    /// requirement.
+    pub fn matches(&self, semver: &str) -> bool {
+        match self {
+            VersionReq::Release(release) => is_release_of(release, semver),
+            _ => semver
+                .split('.')
+                .next()
+                .and_then(|major| major.parse().ok())
+                .is_some_and(|major| self.matches_major(major)),
+        }
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionReq::Major(major) => write!(f, "{}", major),
+            VersionReq::Release(release) => write!(f, "{}", release),
+            VersionReq::Range {
+                min: Some(min),
+                max: None,
+            } => write!(f, "{}+", min),
+            VersionReq::Range { min, max } => {
+                let bounds: Vec<String> = min
+                    .map(|min| format!(">={}", min))
+                    .into_iter()
+                    .chain(max.map(|max| format!("<{}", max)))
+                    .collect();
+                write!(f, "{}", bounds.join(" "))
+            }
+            VersionReq::Lts => write!(f, "lts"),
+            VersionReq::Latest => write!(f, "latest"),
+        }
+    }
+}
+
+/// Parses a range of major versions like `>=17 <22`, normalized to an inclusive lower and an
+/// exclusive upper bound.
+fn parse_range(range: &str) -> Option<VersionReq> {
+    let mut min = None;
+    let mut max = None;
+    for bound in range.split_whitespace() {
+        if let Some(v) = bound.strip_prefix(">=") {
+            min = Some(parse_major(v)?);
+        } else if let Some(v) = bound.strip_prefix('>') {
+            min = Some(parse_major(v)? + 1);
+        } else if let Some(v) = bound.strip_prefix("<=") {
+            max = Some(parse_major(v)? + 1);
+        } else {
+            max = Some(parse_major(bound.strip_prefix('<')?)?);
+        }
+    }
+    // an empty range, like >=22 <17
+    if let (Some(min), Some(max)) = (min, max)
+        && min >= max
+    {
+        return None;
+    }
+    Some(VersionReq::Range { min, max })
+}
+
+fn parse_major(major: &str) -> Option<i64> {
+    if is_number(major) {
+        major.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// A requested JDK, as given on the command line or in `.jlorc`.
+///
+/// The format is `[distribution@]version[-ea][:qualifier]...`, e.g. `21`, `temurin@21`,
+/// `26-ea`, `21:openj9`, `21:jre` or `liberica@21:full`. See [`VersionReq`] for the versions
+/// that may be requested.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JdkSpec {
+    pub distribution: Option<String>,
+    pub version: VersionReq,
+    /// JVM implementation; selects the distribution if none is given.
+    pub jvm_impl: Option<String>,
+    pub variant: Variant,
+    /// Name of the user-defined alias the spec was given as, e.g. `corp-lts`.
+    pub alias: Option<String>,
+}
+
+impl JdkSpec {
+    /// Parses a spec, or the name of a user-defined alias for one.
+    pub fn parse(spec: &str) -> Result<JdkSpec, String> {
+        match alias::lookup(spec)? {
+            Some(aliased_spec) => {
+                let jdk_spec = JdkSpec::parse_spec(&aliased_spec)
+                    .map_err(|e| format!("Invalid alias '{}': {}", spec, e))?;
+                Ok(JdkSpec {
+                    alias: Some(spec.to_string()),
+                    ..jdk_spec
+                })
+            }
+            None => JdkSpec::parse_spec(spec),
+        }
+    }
+
+    fn parse_spec(spec: &str) -> Result<JdkSpec, String> {
+        let (distribution, spec) = match spec.split_once('@') {
+            Some((distribution, spec)) => (Some(distribution), spec),
+            None => (None, spec),
+        };
+
+        let mut parts = spec.split(':');
+        let version = parts.next().unwrap_or_default().trim();
+        let version = version.strip_prefix(JDK_PREFIX).unwrap_or(version);
+        let (version, early_access) = match version.strip_suffix(EARLY_ACCESS_SUFFIX) {
+            Some(version) => (version, true),
+            None => (version, false),
+        };
+        let version = VersionReq::parse(version).ok_or_else(|| {
+            format!(
+                "Unsupported version: '{}'. Use a major version (8, 11, ...), a release like 21.0 or 21.0.4+7, a range like 17+ or >=17 <22, lts, latest or a user-defined alias.",
+                version
+            )
+        })?;
+        if early_access && version.major().is_none() {
+            return Err(format!(
+                "Early-access builds can only be requested for a major version or release, not '{}'.",
+                version
+            ));
+        }
+        let qualifiers: Vec<&str> = parts.collect();
+        let jvm_impl = qualifiers
+            .iter()
+            .copied()
+            .rfind(|qualifier| JVM_IMPLS.contains(qualifier));
+
+        let distribution_name = match (distribution, jvm_impl) {
+            (Some(distribution), _) => distribution,
+            (None, Some(jvm_impl)) => distribution::default_for_jvm_impl(jvm_impl),
+            (None, None) => DEFAULT_DISTRIBUTION,
+        };
+        let distribution_impl = distribution::by_name(distribution_name).ok_or_else(|| {
+            format!(
+                "Unknown distribution: '{}'. Supported distributions are: {}.",
+                distribution_name,
+                DISTRIBUTION_NAMES.join(", ")
+            )
+        })?;
+
+        if let Some(jvm_impl) = jvm_impl
+            && distribution_impl.jvm_impl() != jvm_impl
+        {
+            return Err(format!(
+                "Distribution '{}' only provides the {} JVM, not {}.",
+                distribution_name,
+                distribution_impl.jvm_impl(),
+                jvm_impl
+            ));
+        }
+
+        if early_access && !distribution_impl.supports_early_access() {
+            return Err(format!(
+                "Distribution '{}' doesn't provide early-access builds.",
+                distribution_name
+            ));
+        }
+
+        if let VersionReq::Release(release) = &version
+            && !distribution_impl.supports_exact_versions()
+        {
+            return Err(if release.contains('+') {
+                format!(
+                    "Distribution '{}' doesn't support pinning an exact build.",
+                    distribution_name
+                )
+            } else {
+                format!(
+                    "Distribution '{}' doesn't support release lines like '{}', only major versions.",
+                    distribution_name, release
+                )
+            });
+        }
+
+        let mut bundle_type = None;
+        let mut image_type = None;
+        let mut arch = None;
+        for qualifier in qualifiers {
+            if JVM_IMPLS.contains(&qualifier) {
+                continue;
+            } else if qualifier == STANDARD_BUNDLE {
+                bundle_type = None;
+            } else if distribution_impl.bundle_types().contains(&qualifier) {
+                bundle_type = Some(qualifier.to_string());
+            } else if qualifier == DEFAULT_IMAGE_TYPE {
+                image_type = None;
+            } else if distribution_impl.image_types().contains(&qualifier) {
+                image_type = Some(qualifier.to_string());
+            } else if distribution_impl.archs().contains(&qualifier) {
+                arch = Some(qualifier.to_string()).filter(|arch| arch != env::consts::ARCH);
+            } else {
+                return Err(format!(
+                    "Unsupported qualifier: '{}' for distribution '{}'.",
+                    qualifier, distribution_name
+                ));
+            }
+        }
+
+        Ok(JdkSpec {
+            distribution: distribution.map(str::to_string),
+            version,
+            jvm_impl: jvm_impl.map(str::to_string),
+            variant: Variant {
+                bundle_type,
+                early_access,
+                image_type,
+                arch,
+            },
+            alias: None,
+        })
+    }
+
+    pub fn distribution_name(&self) -> &str {
+        match (&self.distribution, &self.jvm_impl) {
+            (Some(distribution), _) => distribution,
+            (None, Some(jvm_impl)) => distribution::default_for_jvm_impl(jvm_impl),
+            (None, None) => DEFAULT_DISTRIBUTION,
+        }
+    }
+
+    pub fn distribution(&self) -> Box<dyn Distribution> {
+        distribution::by_name(self.distribution_name()).expect("distribution validated on parse")
+    }
+
+    /// Major version to fetch; only available once the spec is resolved.
+    pub fn major(&self) -> i64 {
+        self.version
+            .major()
+            .expect("version resolved before fetching")
+    }
+
+    /// Resolves a range or alias to the newest matching major version available for download.
+    pub fn resolve(&self) -> Result<JdkSpec, String> {
+        if self.version.major().is_some() {
+            return Ok(self.clone());
+        }
+
+        let lts_releases = match self.version {
+            VersionReq::Lts => Some(adoptium::available_lts_releases()?),
+            _ => None,
+        };
+        let major = self
+            .distribution()
+            .available_releases()?
+            .into_iter()
+            .filter(|major| match &lts_releases {
+                Some(lts_releases) => lts_releases.contains(major),
+                None => self.version.matches_major(*major),
+            })
+            .max()
+            .ok_or_else(|| {
+                format!(
+                    "No release of distribution '{}' matches version '{}'.",
+                    self.distribution_name(),
+                    self.version
+                )
+            })?;
+
+        Ok(JdkSpec {
+            version: VersionReq::Major(major),
+            alias: None,
+            ..self.clone()
+        })
+    }
+}
+
+impl fmt::Display for JdkSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(alias) = &self.alias {
+            return write!(f, "{}", alias);
+        }
+        if let Some(distribution) = &self.distribution {
+            write!(f, "{}@", distribution)?;
+        }
+        write!(f, "{}", self.version)?;
+        if self.variant.early_access {
+            write!(f, "{}", EARLY_ACCESS_SUFFIX)?;
+        }
+        if let Some(jvm_impl) = &self.jvm_impl {
+            write!(f, ":{}", jvm_impl)?;
+        }
+        if let Some(bundle_type) = &self.variant.bundle_type {
+            write!(f, ":{}", bundle_type)?;
+        }
+        if let Some(image_type) = &self.variant.image_type {
+            write!(f, ":{}", image_type)?;
+        }
+        if let Some(arch) = &self.variant.arch {
+            write!(f, ":{}", arch)?;
+        }
+        Ok(())
+    }
+}
+
+/// Long-term support releases by the OpenJDK release cadence: 8, 11 and every fourth release
+/// starting with 17.
+pub fn is_lts_release(major: i64) -> bool {
+    major == 8 || major == 11 || (major >= 17 && (major - 17) % 4 == 0)
+}
+
+/// Checks whether a semver (e.g. `21.0.4+7.0.LTS`) belongs to a release line (e.g. `21.0`)
+/// or is an exact build (e.g. `21.0.4+7`, or `21+35` for `21.0.0+35`).
+fn is_release_of(release: &str, semver: &str) -> bool {
+    let (core, build_number) = split_build(release);
+    let (semver_core, semver_build_number) = split_build(semver);
+    // drop pre-release identifiers, as in 26.0.0-beta+20
+    let semver_core = pad_semver(semver_core.split('-').next().unwrap_or_default());
+
+    match build_number {
+        Some(build_number) => {
+            pad_semver(core) == semver_core
+                && semver_build_number.and_then(|s| s.split('.').next()) == Some(build_number)
+        }
+        None => {
+            let components: Vec<&str> = core.split('.').collect();
+            let semver_components: Vec<&str> = semver_core.split('.').collect();
+            semver_components.starts_with(&components)
+        }
+    }
+}
+
+fn split_build(version: &str) -> (&str, Option<&str>) {
+    match version.split_once('+') {
+        Some((core, build_number)) => (core, Some(build_number)),
+        None => (version, None),
+    }
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges() {
+        let at_least_17 = VersionReq::parse("17+").unwrap();
+        assert!(at_least_17.matches("17.0.13+11"));
+        assert!(at_least_17.matches("25.0.1+8"));
+        assert!(!at_least_17.matches("11.0.25+9"));
+
+        let from_17_to_21 = VersionReq::parse(">=17 <22").unwrap();
+        assert!(from_17_to_21.matches("17.0.13+11"));
+        assert!(from_17_to_21.matches("21.0.5+11"));
+        assert!(!from_17_to_21.matches("22.0.2+9"));
+
+        let up_to_17 = VersionReq::parse("<=17").unwrap();
+        assert!(up_to_17.matches("17.0.13+11"));
+        assert!(!up_to_17.matches("21.0.5+11"));
+    }
+
+    #[test]
+    fn release_lines() {
+        assert!(VersionReq::parse("21.0").unwrap().matches("21.0.5+11"));
+        assert!(!VersionReq::parse("21.0").unwrap().matches("21.1.0+3"));
+        assert!(VersionReq::parse("21.0.5").unwrap().matches("21.0.5+11"));
+        assert!(!VersionReq::parse("21.0.5").unwrap().matches("21.0.4+7"));
+        assert!(is_release_of("21.0.5+11", "21.0.5+11.0.LTS"));
+        assert!(!is_release_of("21.0.5+11", "21.0.5+10"));
+        assert!(is_release_of("21+35", "21.0.0+35"));
+    }
+
+    #[test]
+    fn jdk_prefix() {
+        let spec = JdkSpec::parse_spec("jdk-21.0.4+7").unwrap();
+        assert_eq!(spec.version, VersionReq::Release("21.0.4+7".to_string()));
+        assert!(spec.version.matches("21.0.4+7.0.LTS"));
+    }
+
+    #[test]
+    fn invalid_versions() {
+        for version in [
+            "",
+            "7",
+            "abc",
+            "21.x",
+            "1.2.3.4.5",
+            ">=22 <17",
+            ">=17 <17",
+            "+",
+        ] {
+            assert_eq!(VersionReq::parse(version), None, "{}", version);
+        }
+        assert!(
+            JdkSpec::parse_spec("zulu@21.0")
+                .unwrap_err()
+                .contains("doesn't support release lines like '21.0'")
+        );
+    }
+}
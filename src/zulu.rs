@@ -0,0 +1,145 @@
+use crate::distribution::{Distribution, JdkMetadata, archive_type, fetch_json, no_matching_jdk};
+use crate::download::Checksum;
+use crate::spec::{DEFAULT_IMAGE_TYPE, JdkSpec};
+use std::collections::BTreeSet;
+use std::env;
+
+const API_BASE_URL: &str = "https://api.azul.com/metadata/v1/zulu/packages";
+
+/// Azul Zulu, as published by the Azul metadata API.
+pub struct Zulu;
+
+impl Distribution for Zulu {
+    fn name(&self) -> &'static str {
+        "zulu"
+    }
+
+    fn available_releases(&self) -> Result<Vec<i64>, String> {
+        let api_url = format!(
+            "{}&latest=true&page_size=1000",
+            packages_query(DEFAULT_IMAGE_TYPE, env::consts::ARCH)?
+        );
+        let json = fetch_json(&api_url)?;
+
+        let packages = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?;
+
+        let majors: BTreeSet<i64> = packages
+            .iter()
+            .filter_map(|package| package["java_version"][0].as_i64())
+            .collect();
+
+        Ok(majors.into_iter().collect())
+    }
+
+    fn image_types(&self) -> &'static [&'static str] {
+        &["jre"]
+    }
+
+    fn archs(&self) -> &'static [&'static str] {
+        &["x86_64", "x86", "aarch64", "arm", "powerpc64"]
+    }
+
+    fn fetch_metadata(&self, spec: &JdkSpec) -> Result<JdkMetadata, String> {
+        let api_url = format!(
+            "{}&java_version={}&latest=true&page_size=1",
+            packages_query(spec.variant.image_type(), spec.variant.arch())?,
+            spec.major()
+        );
+        let json = fetch_json(&api_url)?;
+
+        let package = json
+            .as_array()
+            .ok_or("Unexpected JSON structure received from API.")?
+            .first()
+            .ok_or_else(|| no_matching_jdk(&api_url))?;
+
+        // The checksum is only part of the package details
+        let package_uuid = package["package_uuid"]
+            .as_str()
+            .ok_or("Incomplete metadata received from API.")?;
+        let details = fetch_json(&format!("{}/{}", API_BASE_URL, package_uuid))?;
+
+        let package_name = details["name"].as_str().unwrap_or("");
+        let download_link = details["download_url"].as_str().unwrap_or("");
+        let checksum = details["sha256_hash"].as_str().unwrap_or("");
+        let semver = java_semver(&details);
+        if semver.is_empty()
+            || package_name.is_empty()
+            || download_link.is_empty()
+            || checksum.is_empty()
+        {
+            return Err("Incomplete metadata received from API.".to_string());
+        }
+
+        Ok(JdkMetadata {
+            semver,
+            release_name: archive_stem(package_name).to_string(),
+            package_name: package_name.to_string(),
+            download_link: download_link.to_string(),
+            checksum: Checksum::Sha256(checksum.to_string()),
+            variant: spec.variant.clone(),
+        })
+    }
+}
+
+fn packages_query(image_type: &str, arch: &str) -> Result<String, String> {
+    Ok(format!(
+        "{}/?os={}&arch={}&archive_type={}&java_package_type={}&javafx_bundled=false&crac_supported=false&release_status=ga&availability_types=CA",
+        API_BASE_URL,
+        zulu_os(),
+        zulu_arch(arch)?,
+        archive_type(),
+        image_type
+    ))
+}
+
+/// Builds a semver like `21.0.4+7` from the `java_version` and `openjdk_build_number` fields.
+fn java_semver(details: &serde_json::Value) -> String {
+    let version: Vec<String> = details["java_version"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|v| v.as_i64())
+                .map(|v| v.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if version.len() < 3 {
+        return String::new();
+    }
+
+    let version = version[..3].join(".");
+    match details["openjdk_build_number"].as_i64() {
+        Some(build) => format!("{}+{}", version, build),
+        None => version,
+    }
+}
+
+fn archive_stem(package_name: &str) -> &str {
+    package_name
+        .strip_suffix(".tar.gz")
+        .or_else(|| package_name.strip_suffix(".zip"))
+        .unwrap_or(package_name)
+}
+
+fn zulu_os() -> &'static str {
+    match env::consts::OS {
+        "linux" | "windows" | "macos" | "solaris" => env::consts::OS,
+        _ => panic!("Unknown OS: {}", env::consts::OS),
+    }
+}
+
+fn zulu_arch(arch: &str) -> Result<&str, String> {
+    match arch {
+        "x86_64" => Ok("x64"),
+        "x86" => Ok("x86"),
+        "powerpc64" => Ok("ppc64"),
+        "arm" | "aarch64" => Ok(arch),
+        "sparc64" => Ok("sparcv9"),
+        _ => Err(format!("Unsupported architecture: {}", arch)),
+    }
+}
@@ -35,7 +35,7 @@ fn init() {
         lines,
         [
             "# Java version configured by J'Lo - https://github.com/java-loader/jlo",
-            "25"
+            "java = \"25\""
         ]
     );
 
@@ -84,3 +84,847 @@ fn env() {
     }
     temp_dir.close().unwrap();
 }
+
+#[test]
+fn env_unknown_distribution() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "acme@25"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Unknown distribution: 'acme'. Supported distributions are: temurin,",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_unsupported_qualifier() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "temurin@25:full"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Unsupported qualifier: 'full' for distribution 'temurin'.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_unsupported_arch() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "corretto@21:s390x"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Unsupported qualifier: 's390x' for distribution 'corretto'.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_unsupported_jvm_impl() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "temurin@25:openj9"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Distribution 'temurin' only provides the hotspot JVM, not openj9.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_unsupported_early_access() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "zulu@26-ea"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Distribution 'zulu' doesn't provide early-access builds.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_unsupported_exact_version() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "zulu@jdk-21.0.4+7"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Distribution 'zulu' doesn't support pinning an exact build.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_early_access_alias() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "lts-ea"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Early-access builds can only be requested for a major version or release, not 'lts'.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_alias() {
+    let jlo_home = tempfile::tempdir().unwrap();
+    std::fs::write(
+        jlo_home.path().join("aliases"),
+        "# aliases for testing\ncorp-debug = 25:debugimage\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", jlo_home.path())
+        .env("JLO_HOME", jlo_home.path())
+        .args(["env", "corp-debug"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Image type 'debugimage' contains no Java runtime. To install it, run: `jlo update corp-debug`",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_tool_versions_unknown_vendor() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join(".tool-versions"),
+        "nodejs 22.11.0\njava acme-21.0.2\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path()).env("JLO_HOME", home_dir.path().join(".jlo")).current_dir(project_dir.path())
+        .arg("env")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Invalid Java version specified in '.tool-versions': Unknown vendor in version: 'acme-21.0.2'.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_jlorc_precedence() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "25:debugimage\n").unwrap();
+    std::fs::write(project_dir.path().join(".sdkmanrc"), "java=21.0.2-acme\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(project_dir.path())
+        .arg("env")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Image type 'debugimage' contains no Java runtime.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn init_from_gradle_toolchain() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join("build.gradle.kts"),
+        "java {\n    toolchain {\n        languageVersion = JavaLanguageVersion.of(17)\n    }\n}\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(project_dir.path())
+        .arg("init")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "Using Java 17 as required by 'build.gradle.kts'",
+        ))
+        .stdout(predicate::str::contains(
+            "Created config file '.jlorc' with Java 17",
+        ));
+
+    let config = std::fs::read_to_string(project_dir.path().join(".jlorc")).unwrap();
+    assert!(config.ends_with("\njava = \"17\"\n"));
+}
+
+#[test]
+fn env_maven_release() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join("pom.xml"),
+        "<project><properties><maven.compiler.release>7</maven.compiler.release></properties></project>",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(project_dir.path())
+        .arg("env")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Invalid Java version specified in 'pom.xml': Unsupported version: '7'.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_structured_config() {
+    // an installed JDK, so that nothing is downloaded
+    let home_dir = tempfile::tempdir().unwrap();
+    install_fake_jdk(home_dir.path(), "25.0.1+8");
+
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join(".jlorc"),
+        "java = \"25\"\njlo_version = \"0.1\"\n\n[env]\nMAVEN_OPTS = \"-Xmx2g -Dgreeting=\\\"Hi $USER\\\"\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env_remove("MAVEN_OPTS")
+        .arg("env")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("export JAVA_HOME=").and(predicate::str::contains(
+                r#"export MAVEN_OPTS="-Xmx2g -Dgreeting=\"Hi \$USER\"""#,
+            )),
+        );
+}
+
+#[test]
+fn env_reserved_variable() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join(".jlorc"),
+        "java = \"25\"\n\n[env]\nJLO_CHANGED_VARIABLES = \"x\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(project_dir.path())
+        .arg("env")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Environment variable 'JLO_CHANGED_VARIABLES' is set by J'Lo",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_required_jlo_version() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join(".jlorc"),
+        "java = \"25\"\njlo_version = \"99.0\"\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(project_dir.path())
+        .arg("env")
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "This project requires J'Lo 99.0 or newer",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn env_parent_directory_config() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    let module_dir = project_dir.path().join("module");
+    let source_dir = module_dir.join("src").join("main");
+    std::fs::create_dir_all(&source_dir).unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "25:debugimage\n").unwrap();
+
+    // the config of the project applies to all subdirectories
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(&source_dir)
+        .arg("env")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("run: `jlo update 25:debugimage`"));
+
+    // nested config files override outer ones
+    std::fs::write(module_dir.join(".jlorc"), "21:debugimage\n").unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(&source_dir)
+        .arg("env")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("run: `jlo update 21:debugimage`"));
+
+    // the search ends at the boundary
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .current_dir(&source_dir)
+        .env("JLO_SEARCH_BOUNDARY", module_dir.join("src"))
+        .arg("env")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("run: `jlo init` first."));
+}
+
+#[test]
+fn default_version() {
+    // an installed JDK, so that nothing is downloaded
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let jlo_home = home_dir.path().join(".jlo");
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .args(["default", "21"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Default Java version set to 21"));
+
+    assert_eq!(
+        std::fs::read_link(jlo_home.join("current")).unwrap(),
+        java_home
+    );
+
+    // outside of projects, the default is used
+    let project_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .arg("env")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "export JAVA_HOME=\"{}\"",
+            java_home.to_string_lossy()
+        )));
+}
+
+#[test]
+fn deactivate() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_bin = jdk_base_dir(home_dir.path()).join("21.0.5+11").join("bin");
+    let path = std::env::join_paths([
+        java_bin.as_path(),
+        "/usr/bin".as_ref(),
+        "/opt/tool".as_ref(),
+    ])
+    .unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("PATH", path)
+        .env(
+            "JLO_CHANGED_VARIABLES",
+            "JAVA_HOME PATH JLO_PATH_ENTRY MAVEN_OPTS",
+        )
+        .env("JLO_ORIG_JAVA_HOME", "/opt/java")
+        .env("JLO_ORIG_PATH", "/usr/bin")
+        .env("JLO_PATH_ENTRY", &java_bin)
+        .arg("deactivate")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("export JAVA_HOME=\"/opt/java\"\n")
+                .and(predicate::str::contains(
+                    "export PATH=\"/usr/bin:/opt/tool\"\n",
+                ))
+                .and(predicate::str::contains("unset JLO_PATH_ENTRY\n"))
+                .and(predicate::str::contains("unset MAVEN_OPTS\n"))
+                .and(predicate::str::contains("unset JLO_CHANGED_VARIABLES\n")),
+        );
+}
+
+#[test]
+fn env_replaces_path_entry() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let old_java_bin = install_fake_jdk(home_dir.path(), "17.0.9+9").join("bin");
+    let java_bin = install_fake_jdk(home_dir.path(), "21.0.5+11").join("bin");
+    let path = std::env::join_paths([old_java_bin.as_path(), "/usr/bin".as_ref()]).unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env("PATH", path)
+        .env("JLO_PATH_ENTRY", &old_java_bin)
+        .args(["env", "21"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(format!(
+                "export PATH=\"{}:/usr/bin\"\n",
+                java_bin.to_string_lossy()
+            ))
+            .and(predicate::str::contains(format!(
+                "export JLO_PATH_ENTRY=\"{}\"\n",
+                java_bin.to_string_lossy()
+            ))),
+        );
+}
+
+#[test]
+fn env_fish() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env("JAVA_HOME", "/opt/it's $java")
+        .env("PATH", "/usr/bin:/bin")
+        .args(["env", "21", "--shell", "fish"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(r"set -gx JLO_ORIG_JAVA_HOME '/opt/it\'s $java'").and(
+                predicate::str::contains(format!(
+                    "set -gx PATH '{}' '/usr/bin' '/bin'\n",
+                    java_home.join("bin").to_string_lossy()
+                )),
+            ),
+        );
+}
+
+#[test]
+fn exec() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args([
+            "exec",
+            "21",
+            "--",
+            "sh",
+            "-c",
+            "echo \"$JAVA_HOME\" \"$JLO_CHANGED_VARIABLES\"; exit 3",
+        ])
+        .env_remove("JLO_CHANGED_VARIABLES")
+        .current_dir(home_dir.path())
+        .assert()
+        .code(3)
+        .stdout(format!("{} \n", java_home.to_string_lossy()))
+        .stderr("");
+}
+
+#[cfg(unix)]
+#[test]
+fn shims() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+    std::fs::write(
+        java_home.join("bin").join("java"),
+        "#!/bin/sh\necho \"java $1 $JAVA_HOME\"\n",
+    )
+    .unwrap();
+    std::process::Command::new("chmod")
+        .args(["+x", &java_home.join("bin").join("java").to_string_lossy()])
+        .status()
+        .unwrap();
+    let jlo_home = home_dir.path().join(".jlo");
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .arg("reshim")
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("Created 1 shims"));
+
+    // the shim runs the tool of the JDK configured for the current directory
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "21\n").unwrap();
+    let mut cmd = Command::new(jlo_home.join("shims").join("java"));
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .arg("-version")
+        .assert()
+        .success()
+        .stdout(format!("java -version {}\n", java_home.to_string_lossy()));
+}
+
+#[test]
+fn home_and_which() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["home", "21"])
+        .assert()
+        .success()
+        .stdout(format!("{}\n", java_home.to_string_lossy()));
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["which", "java", "21"])
+        .assert()
+        .success()
+        .stdout(format!(
+            "{}\n",
+            java_home.join("bin").join("java").to_string_lossy()
+        ));
+
+    // nothing is downloaded without --install
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["home", "17"])
+        .assert()
+        .failure()
+        .stdout("")
+        .stderr(predicate::str::contains(
+            "No installed JDK matches Java 17.",
+        ));
+}
+
+#[test]
+fn env_non_runtime_image_type() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .args(["env", "25:debugimage"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Image type 'debugimage' contains no Java runtime.",
+        ))
+        .stdout("");
+}
+
+#[test]
+fn hook_env() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let jlo_home = home_dir.path().join(".jlo");
+
+    // entering a project
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "21\n").unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env_remove("JLO_PROJECT_DIR")
+        .arg("hook-env")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(format!(
+                "export JAVA_HOME=\"{}\"",
+                java_home.to_string_lossy()
+            ))
+            .and(predicate::str::contains(format!(
+                "export JLO_PROJECT_DIR=\"{}\"",
+                project_dir.path().to_string_lossy()
+            ))),
+        );
+
+    // leaving it again, without a default Java version
+    let other_dir = tempfile::tempdir().unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(other_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env("JAVA_HOME", &java_home)
+        .env("JLO_PROJECT_DIR", project_dir.path())
+        .env("JLO_CHANGED_VARIABLES", "JAVA_HOME JLO_PROJECT_DIR")
+        .env("JLO_ORIG_JAVA_HOME", "/opt/java")
+        .arg("hook-env")
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains("export JAVA_HOME=\"/opt/java\"\n")
+                .and(predicate::str::contains("unset JLO_PROJECT_DIR\n")),
+        );
+}
+
+#[test]
+fn hook_env_fast_path() {
+    let home_dir = tempfile::tempdir().unwrap();
+    install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let jlo_home = home_dir.path().join(".jlo");
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "21\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    let output = cmd
+        .current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env_remove("JLO_PROJECT_DIR")
+        .env_remove("JLO_HOOK_STATE")
+        .args(["hook-env", "--shell", "posix"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let state = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("export JLO_HOOK_STATE=\""))
+        .and_then(|state| state.strip_suffix('"'))
+        .unwrap();
+
+    // nothing to do, as the configuration didn't change
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env("JLO_HOOK_STATE", state)
+        .args(["hook-env", "--shell", "posix"])
+        .assert()
+        .success()
+        .stdout("");
+
+    // a missing JDK is never downloaded
+    std::fs::write(project_dir.path().join(".jlorc"), "17\n").unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env("JLO_HOOK_STATE", state)
+        .args(["hook-env", "--shell", "posix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("JAVA_HOME").not())
+        .stderr("Java 17 is not installed yet. To install it, run: `jlo update 17`\n");
+}
+
+#[test]
+fn hook_env_default_change() {
+    let home_dir = tempfile::tempdir().unwrap();
+    install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let java_home_17 = install_fake_jdk(home_dir.path(), "17.0.13+11");
+    let jlo_home = home_dir.path().join(".jlo");
+    std::fs::create_dir_all(&jlo_home).unwrap();
+    std::fs::write(jlo_home.join("default"), "21\n").unwrap();
+    let other_dir = tempfile::tempdir().unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    let output = cmd
+        .current_dir(other_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env_remove("JAVA_HOME")
+        .env_remove("JLO_PROJECT_DIR")
+        .env_remove("JLO_HOOK_STATE")
+        .env_remove("JLO_CHANGED_VARIABLES")
+        .args(["hook-env", "--shell", "posix"])
+        .output()
+        .unwrap();
+    // the environment set up for the default, as exported to the shell
+    let exports: Vec<(String, String)> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("export "))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.trim_matches('"').to_string()))
+        .collect();
+    assert!(exports.iter().any(|(name, _)| name == "JLO_HOOK_STATE"));
+
+    // changing the default switches to it
+    std::fs::write(jlo_home.join("default"), "17\n").unwrap();
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(other_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", &jlo_home)
+        .env_remove("JLO_PROJECT_DIR")
+        .envs(exports)
+        .args(["hook-env", "--shell", "posix"])
+        .assert()
+        .success()
+        .stdout(
+            predicate::str::contains(format!(
+                "export JAVA_HOME=\"{}\"",
+                java_home_17.to_string_lossy()
+            ))
+            .and(predicate::str::contains("export JLO_HOOK_STATE=")),
+        );
+}
+
+#[test]
+fn hook_env_missing_jdk() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(project_dir.path().join(".jlorc"), "21\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    let output = cmd
+        .current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env_remove("JLO_PROJECT_DIR")
+        .env_remove("JLO_HOOK_STATE")
+        .env_remove("JLO_CHANGED_VARIABLES")
+        .args(["hook-env", "--shell", "posix"])
+        .output()
+        .unwrap();
+    let exports: Vec<(String, String)> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter_map(|line| line.strip_prefix("export "))
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| (name.to_string(), value.trim_matches('"').to_string()))
+        .collect();
+    assert!(!exports.iter().any(|(name, _)| name == "JLO_HOOK_STATE"));
+
+    // the JDK installed in the meantime is used, even though the configuration didn't change
+    let java_home = install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env_remove("JLO_HOOK_STATE")
+        .envs(exports)
+        .args(["hook-env", "--shell", "posix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(format!(
+            "export JAVA_HOME=\"{}\"",
+            java_home.to_string_lossy()
+        )));
+}
+
+#[test]
+fn hook_env_invalid_build_file() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let project_dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project_dir.path().join("pom.xml"),
+        "<project><properties><maven.compiler.release>7</maven.compiler.release></properties></project>",
+    )
+    .unwrap();
+
+    // a build file without a usable Java version doesn't make a project
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(project_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env_remove("JLO_PROJECT_DIR")
+        .env_remove("JLO_HOOK_STATE")
+        .env_remove("JLO_CHANGED_VARIABLES")
+        .args(["hook-env", "--shell", "posix"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("JAVA_HOME").not())
+        .stderr("");
+}
+
+#[cfg(unix)]
+#[test]
+fn exec_replaces_path_entry() {
+    let home_dir = tempfile::tempdir().unwrap();
+    let java_home_17 = install_fake_jdk(home_dir.path(), "17.0.13+11");
+    let java_home_21 = install_fake_jdk(home_dir.path(), "21.0.5+11");
+    let bin_17 = java_home_17.join("bin").to_string_lossy().into_owned();
+    let bin_21 = java_home_21.join("bin").to_string_lossy().into_owned();
+
+    // the environment of a shell after `jlo env 17`
+    let mut cmd = Command::cargo_bin("jlo-bin").unwrap();
+    cmd.current_dir(home_dir.path())
+        .env("HOME", home_dir.path())
+        .env("JLO_HOME", home_dir.path().join(".jlo"))
+        .env("JAVA_HOME", &java_home_17)
+        .env("PATH", format!("{}:/usr/bin:/bin", bin_17))
+        .env("JLO_PATH_ENTRY", &bin_17)
+        .args([
+            "exec",
+            "21",
+            "--",
+            "sh",
+            "-c",
+            "echo \"$JLO_PATH_ENTRY\"; echo \"$PATH\"",
+        ])
+        .assert()
+        .success()
+        .stdout(format!("{}\n{}:/usr/bin:/bin\n", bin_21, bin_21));
+}
+
+fn jdk_base_dir(home_dir: &std::path::Path) -> std::path::PathBuf {
+    match cfg!(target_os = "macos") {
+        true => home_dir.join("Library/Java/JavaVirtualMachines"),
+        false => home_dir.join("jdks"),
+    }
+}
+
+/// Creates a minimal JDK installation, as if installed by jlo.
+fn install_fake_jdk(home_dir: &std::path::Path, version: &str) -> std::path::PathBuf {
+    let java_home = jdk_base_dir(home_dir).join(version);
+    std::fs::create_dir_all(java_home.join("bin")).unwrap();
+    std::fs::write(java_home.join("bin").join("java"), "").unwrap();
+    std::fs::write(java_home.join(".jlo-managed"), "").unwrap();
+    java_home
+}